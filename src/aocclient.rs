@@ -1,17 +1,62 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::error;
 use std::fmt;
+use std::io;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::RwLock;
+use std::thread::sleep;
 use std::time::Duration;
+use std::time::Instant;
 
 use aho_corasick::AhoCorasick;
+use chrono::DateTime;
+use chrono::Utc;
 use http::HeaderValue;
+use http::Method;
+use http::StatusCode;
 use log::debug;
+use log::info;
+use log::warn;
 use regex::Regex;
 use reqwest::Url;
 
+use crate::calendar;
+use cache::InputCache;
+use replay::Exchange;
+
+mod archive;
+mod cache;
+mod leaderboard;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+mod page;
+mod replay;
+mod retry;
+mod settings;
+mod throttle;
+
+pub use archive::ArchivedResponse;
+pub use archive::SubmissionArchive;
+pub use leaderboard::Leaderboard;
+pub use leaderboard::Member;
+pub use page::PuzzleExamples;
+pub use replay::TrafficMode;
+pub use retry::RetryPolicy;
+pub use settings::HttpSettings;
+pub use throttle::ThrottlePolicy;
+
+/// Address of the real site, which the clients send requests to unless given another.
+pub const BASE_URL: &str = "https://adventofcode.com/";
+
+/// Heading of the second part of a puzzle description. It appears only after solving the
+/// first part, so a cached description without it is downloaded again.
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Replaces the session cookie wherever it could be logged or saved.
+const REDACTED: &str = "<redacted>";
+
 /// Implements a CookieStore for the sole purpose of transmitting the Advent of Code
 /// session cookie. Will not store any other cookies.
 struct AocSessionCookieStore {
@@ -28,6 +73,15 @@ impl AocSessionCookieStore {
     }
 }
 
+impl fmt::Debug for AocSessionCookieStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cookie = self.cookie.read().unwrap().as_ref().map(|_| REDACTED);
+        f.debug_struct("AocSessionCookieStore")
+            .field("cookie", &cookie)
+            .finish()
+    }
+}
+
 impl reqwest::cookie::CookieStore for AocSessionCookieStore {
     fn set_cookies(&self, _cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, _url: &Url) {}
     fn cookies(&self, _url: &Url) -> Option<HeaderValue> {
@@ -37,7 +91,11 @@ impl reqwest::cookie::CookieStore for AocSessionCookieStore {
                 None
             }
             Some(cookie) => match HeaderValue::from_str(format!("session={cookie}").as_str()) {
-                Ok(hv) => Some(hv),
+                Ok(mut hv) => {
+                    // Keeps the cookie out of the debug logs of reqwest and hyper.
+                    hv.set_sensitive(true);
+                    Some(hv)
+                }
                 Err(e) => {
                     debug!("failed to create HeaderValue from cookie string: {e}");
                     None
@@ -49,32 +107,431 @@ impl reqwest::cookie::CookieStore for AocSessionCookieStore {
 
 #[derive(Debug)]
 pub enum Error {
-    HttpError(reqwest::Error),
+    Http(reqwest::Error),
+    Io(io::Error),
+    /// The server rejected the request with 400, which AoC does when the session
+    /// cookie is missing or no longer valid.
+    SessionExpired,
+    /// The server responded with 404 for the given path, usually because the puzzle
+    /// has not been unlocked yet.
+    NotUnlocked(String),
+    ServerFailure(StatusCode),
+    /// The submission response page did not match any known outcome.
+    UnparseableResponse(String),
+    Json(serde_json::Error),
+    /// The puzzle unlocks at the given time, so the request was not sent.
+    Locked(DateTime<Utc>),
+    /// The event of the year has no puzzle for the day, so the request was not sent.
+    NoSuchPuzzle(i16, i8),
+    UnexpectedStatus(StatusCode),
+    /// No response was recorded for the request in replay mode.
+    ReplayMiss(String),
+    /// The request was not sent because the client is offline.
+    Offline(String),
+    /// The answer key of a local puzzle source is invalid or lacks the puzzle.
+    AnswerKey(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "error") // TODO
+        match self {
+            Self::Http(e) => write!(f, "HTTP request failed: {e}"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::SessionExpired => write!(f, "the session cookie is missing or has expired"),
+            Self::NotUnlocked(path) => {
+                write!(
+                    f,
+                    "{path} was not found, the puzzle is probably not unlocked yet"
+                )
+            }
+            Self::ServerFailure(status) => write!(f, "Advent of Code server error: {status}"),
+            Self::UnparseableResponse(reason) => {
+                write!(f, "could not understand the submission response: {reason}")
+            }
+            Self::Json(e) => write!(f, "invalid JSON in response: {e}"),
+            Self::Locked(unlock) => write!(f, "the puzzle unlocks at {unlock}"),
+            Self::NoSuchPuzzle(year, day) => write!(f, "{year} has no puzzle for day {day}"),
+            Self::UnexpectedStatus(status) => write!(f, "unexpected response status {status}"),
+            Self::ReplayMiss(request) => write!(f, "no recorded response for {request}"),
+            Self::Offline(request) => write!(f, "not sending {request} in offline mode"),
+            Self::AnswerKey(reason) => write!(f, "bad answer key: {reason}"),
+        }
     }
 }
 
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
-            Self::HttpError(ref e) => Some(e),
+            Self::Http(ref e) => Some(e),
+            Self::Io(ref e) => Some(e),
+            Self::Json(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Whether the request that failed with this error may succeed if simply retried.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Http(e) => e.is_timeout() || e.is_connect(),
+            Self::ServerFailure(status) => matches!(
+                *status,
+                StatusCode::INTERNAL_SERVER_ERROR
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            _ => false,
         }
     }
 }
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Error {
-        Error::HttpError(e)
+        Error::Http(e)
     }
 }
 
-pub struct AocClient {
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error {
+        Error::Json(e)
+    }
+}
+
+/// Settings for creating an AocClient.
+#[derive(Clone, Debug, Default)]
+pub struct ClientOptions {
+    pub retry: RetryPolicy,
+    /// Requests are not throttled if None.
+    pub throttle: Option<ThrottlePolicy>,
+    pub traffic: TrafficMode,
+    /// Log every request with its status, timing and the start of the response.
+    pub trace_http: bool,
+    /// Refuse to send any request, so that only the caches and replayed traffic are used.
+    pub offline: bool,
+    /// Timeouts, proxy, certificates and user agent of the HTTP client.
+    pub http: HttpSettings,
+}
+
+/// Settings and request handling shared by the blocking and the async client.
+#[derive(Clone)]
+struct ClientCore {
     base_url: String,
+    // We need to use an Arc here because reqwest::ClientBuilder requires an
+    // Arc<C> of CookieStore:
+    // https://docs.rs/reqwest/latest/reqwest/blocking/struct.ClientBuilder.html
     cookie_store: Arc<AocSessionCookieStore>,
+    input_cache: Option<InputCache>,
+    archive: Option<SubmissionArchive>,
+    retry: RetryPolicy,
+    throttle: Option<ThrottlePolicy>,
+    traffic: TrafficMode,
+    trace_http: bool,
+    offline: bool,
+}
+
+/// The parts of a request that identify it in recorded traffic.
+struct RequestSummary {
+    method: String,
+    url: String,
+    path: String,
+    body: String,
+}
+
+impl RequestSummary {
+    fn new(method: &Method, url: &Url, body: Option<&[u8]>) -> RequestSummary {
+        RequestSummary {
+            method: method.to_string(),
+            url: url.to_string(),
+            path: String::from(url.path()),
+            body: body
+                .map(|body| String::from_utf8_lossy(body).into_owned())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn key(&self) -> String {
+        Exchange::key(&self.method, &self.path, &self.body)
+    }
+
+    /// Returns the year, day, level and answer if the request submits an answer.
+    fn submission(&self) -> Option<(i16, i8, i8, String)> {
+        static PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"/(\d+)/day/(\d+)/answer$")
+                .expect("Regex compilation for RequestSummary::submission()")
+        });
+        if self.method != Method::POST.as_str() {
+            return None;
+        }
+        let caps = PATH_RE.captures(&self.path)?;
+        let mut form = Url::parse("http://localhost/").expect("URL for parsing forms");
+        form.set_query(Some(&self.body));
+        let field = |name| {
+            form.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+        Some((
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            field("level")?.parse().ok()?,
+            field("answer")?,
+        ))
+    }
+}
+
+impl ClientCore {
+    fn new(base_url: &str, options: ClientOptions) -> ClientCore {
+        let mut base_url = String::from(base_url);
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+        ClientCore {
+            base_url,
+            cookie_store: Arc::new(AocSessionCookieStore::new()),
+            input_cache: None,
+            archive: None,
+            retry: options.retry,
+            throttle: options.throttle,
+            traffic: options.traffic,
+            trace_http: options.trace_http,
+            offline: options.offline,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        self.base_url.clone() + path
+    }
+
+    fn cached_input(&self, year: i16, day: i8) -> Option<String> {
+        let cache = self.input_cache.as_ref()?;
+        let Some(input) = cache.read_input(year, day) else {
+            debug!("puzzle input for {year} day {day} is not cached");
+            return None;
+        };
+        info!(
+            "using cached puzzle input {}",
+            cache.input_path(year, day).display()
+        );
+        Some(input)
+    }
+
+    fn is_input_cached(&self, year: i16, day: i8) -> bool {
+        self.input_cache
+            .as_ref()
+            .is_some_and(|cache| cache.input_path(year, day).exists())
+    }
+
+    fn is_description_cached(&self, year: i16, day: i8) -> bool {
+        self.input_cache
+            .as_ref()
+            .and_then(|cache| cache.read_description(year, day))
+            .is_some_and(|description| description.contains(PART_TWO_HEADING))
+    }
+
+    fn cache_input(&self, year: i16, day: i8, input: &str) -> Result<(), Error> {
+        if let Some(cache) = &self.input_cache {
+            cache.write_input(year, day, input)?;
+            debug!(
+                "cached puzzle input {}",
+                cache.input_path(year, day).display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the cached puzzle description if it contains both parts.
+    fn cached_description(&self, year: i16, day: i8) -> Option<String> {
+        let cache = self.input_cache.as_ref()?;
+        let description = cache.read_description(year, day)?;
+        if !description.contains(PART_TWO_HEADING) {
+            return None;
+        }
+        info!(
+            "using cached puzzle description {}",
+            cache.description_path(year, day).display()
+        );
+        Some(description)
+    }
+
+    fn cache_description(&self, year: i16, day: i8, description: &str) -> Result<(), Error> {
+        if let Some(cache) = &self.input_cache {
+            cache.write_description(year, day, description)?;
+            debug!(
+                "cached puzzle description {}",
+                cache.description_path(year, day).display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Keeps the response to an answer submission in the archive, whatever its status,
+    /// and logs what AoC said, so that the outcome can be checked if the response is
+    /// misclassified or the submission failed.
+    fn archive_submission(&self, request: &RequestSummary, status: StatusCode, body: &str) {
+        let Some((year, day, level, answer)) = request.submission() else {
+            return;
+        };
+        if let Some(text) = page::article_text(body) {
+            info!("AoC says: {text}");
+        }
+        if let Some(archive) = &self.archive {
+            let response = ArchivedResponse::new(
+                year,
+                day,
+                level,
+                &answer,
+                status.as_u16(),
+                &self.redact(body),
+            );
+            match archive.save(&response) {
+                Ok(path) => debug!("archived submission response {}", path.display()),
+                Err(e) => warn!("failed to archive submission response: {e}"),
+            }
+        }
+    }
+
+    /// Returns the recorded response to the request in replay mode, or None if the
+    /// request should be sent.
+    fn replay(&self, request: &RequestSummary) -> Option<Result<String, Error>> {
+        let TrafficMode::Replay(dir) = &self.traffic else {
+            return None;
+        };
+        let key = request.key();
+        let Some(exchange) = Exchange::load(dir, &key) else {
+            return Some(Err(Error::ReplayMiss(key)));
+        };
+        let Ok(status) = StatusCode::from_u16(exchange.status) else {
+            return Some(Err(Error::ReplayMiss(key)));
+        };
+        debug!(
+            "replaying recorded response for {} {}",
+            request.method, request.path
+        );
+        if self.trace_http {
+            info!(
+                "{} {} -> {} (replayed): {}",
+                request.method,
+                request.url,
+                exchange.status,
+                truncate_body(&exchange.response)
+            );
+        }
+        Some(check_status(
+            status,
+            request.path.clone(),
+            exchange.response,
+        ))
+    }
+
+    /// Whether another attempt may follow if this attempt at the request fails. Only GET
+    /// requests are retried.
+    fn may_retry(&self, method: &Method, attempt: u32) -> bool {
+        *method == Method::GET && attempt < self.retry.max_attempts
+    }
+
+    /// Returns how long to wait before retrying the request to the path after the attempt
+    /// failed with the error, or None if the error is not worth a retry.
+    fn retry_backoff(&self, path: &str, attempt: u32, e: &Error) -> Option<Duration> {
+        if !e.is_transient() {
+            return None;
+        }
+        let backoff = self.retry.backoff(attempt);
+        warn!(
+            "{path} failed: {e}, retrying in {backoff:?} (attempt {attempt} of {})",
+            self.retry.max_attempts
+        );
+        Some(backoff)
+    }
+
+    /// Refuses the request in offline mode.
+    fn check_online(&self, request: &RequestSummary) -> Result<(), Error> {
+        if self.offline {
+            return Err(Error::Offline(format!(
+                "{} {}",
+                request.method, request.path
+            )));
+        }
+        Ok(())
+    }
+
+    /// Replaces the session cookie in the text, so that it cannot leak into logs, saved
+    /// files or error messages.
+    fn redact(&self, text: &str) -> String {
+        match &*self.cookie_store.cookie.read().unwrap() {
+            Some(cookie) if !cookie.is_empty() => text.replace(cookie.as_str(), REDACTED),
+            _ => String::from(text),
+        }
+    }
+
+    fn trace_failure(&self, request: &RequestSummary, e: &reqwest::Error, elapsed: Duration) {
+        if self.trace_http {
+            info!(
+                "{} {} failed after {elapsed:?}: {}",
+                request.method,
+                request.url,
+                self.redact(&e.to_string())
+            );
+        }
+    }
+
+    /// Describes the response for the HTTP trace, without the session cookie.
+    fn trace_line(
+        &self,
+        request: &RequestSummary,
+        status: StatusCode,
+        text: &str,
+        elapsed: Duration,
+    ) -> String {
+        format!(
+            "{} {} -> {status} in {elapsed:?}: {}",
+            request.method,
+            request.url,
+            truncate_body(&self.redact(text))
+        )
+    }
+
+    /// Traces the response, archives it if it answers a submission and saves it in record
+    /// mode, then translates its status. The session cookie is redacted from
+    /// the trace and the saved responses, but the body is returned as received.
+    fn finish(
+        &self,
+        request: RequestSummary,
+        status: StatusCode,
+        text: String,
+        elapsed: Duration,
+    ) -> Result<String, Error> {
+        if self.trace_http {
+            info!("{}", self.trace_line(&request, status, &text, elapsed));
+        }
+        self.archive_submission(&request, status, &text);
+        if let TrafficMode::Record(dir) = &self.traffic {
+            Exchange {
+                method: request.method,
+                path: request.path.clone(),
+                body: request.body,
+                status: status.as_u16(),
+                response: self.redact(&text),
+            }
+            .save(dir)?;
+        }
+        check_status(status, request.path, text)
+    }
+
+    fn set_cookie(&self, cookie: &str) {
+        *self.cookie_store.cookie.write().unwrap() = Some(String::from(cookie));
+    }
+}
+
+pub struct AocClient {
+    core: ClientCore,
     client: reqwest::blocking::Client,
 }
 
@@ -84,15 +541,22 @@ pub enum ValidationResult {
     Rejected,
     RejectedTooLow,
     RejectedTooHigh,
+    /// The answer was wrong and AoC requires waiting before the next attempt. Answers
+    /// rejected as too low or too high are reported as such even if a wait is attached.
+    RejectedWithTimeout(Duration),
+    /// The part being submitted is either already solved or not unlocked yet.
+    WrongLevel,
     Throttled(Duration),
 }
 
-fn parse_validation_response(text: &str) -> ValidationResult {
-    static PATTERNS: &[&str; 4] = &[
+fn parse_validation_response(text: &str) -> Result<ValidationResult, Error> {
+    static PATTERNS: &[&str; 6] = &[
         "You gave an answer too recently",
         "That's the right answer",
         "your answer is too high",
         "your answer is too low",
+        "That's not the right answer",
+        "You don't seem to be solving the right level",
     ];
     static AC: LazyLock<AhoCorasick> = LazyLock::new(|| {
         AhoCorasick::new(PATTERNS).expect("AhoCorasick automaton for parse_validation_response()")
@@ -101,12 +565,18 @@ fn parse_validation_response(text: &str) -> ValidationResult {
         Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")
             .expect("Regex compilation for parse_validation_response()")
     });
-    let mut result = ValidationResult::Rejected;
+    static PENALTY_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"(?i)please wait (one|\d+) minutes? before trying again")
+            .expect("Regex compilation for parse_validation_response()")
+    });
+    let mut result = None;
     for mat in AC.find_iter(text) {
-        result = match mat.pattern().as_usize() {
-            0 => TIMEOUT_RE
-                .captures(text)
-                .map_or(ValidationResult::Rejected, |caps| {
+        result = Some(match mat.pattern().as_usize() {
+            0 => TIMEOUT_RE.captures(text).map_or(
+                Err(Error::UnparseableResponse(String::from(
+                    "throttled without a wait time",
+                ))),
+                |caps| {
                     let minutes = caps.get(1).map_or(0, |minutes| {
                         if minutes.is_empty() {
                             0
@@ -122,71 +592,232 @@ fn parse_validation_response(text: &str) -> ValidationResult {
                         .as_str()
                         .parse::<u64>()
                         .expect("couldn't parse seconds");
-                    ValidationResult::Throttled(Duration::from_secs(seconds + 60 * minutes))
-                }),
-            1 => ValidationResult::Accepted,
-            2 => ValidationResult::RejectedTooHigh,
-            3 => ValidationResult::RejectedTooLow,
+                    Ok(ValidationResult::Throttled(Duration::from_secs(
+                        seconds + 60 * minutes,
+                    )))
+                },
+            ),
+            1 => Ok(ValidationResult::Accepted),
+            2 => Ok(ValidationResult::RejectedTooHigh),
+            3 => Ok(ValidationResult::RejectedTooLow),
+            4 => Ok(PENALTY_RE
+                .captures(text)
+                .map_or(ValidationResult::Rejected, |caps| {
+                    let minutes = match &caps[1] {
+                        "one" => 1,
+                        minutes => minutes.parse::<u64>().expect("couldn't parse minutes"),
+                    };
+                    ValidationResult::RejectedWithTimeout(Duration::from_secs(60 * minutes))
+                })),
+            5 => Ok(ValidationResult::WrongLevel),
             _ => panic!("did not expect pattern from AhoCorasick"),
-        }
+        })
+    }
+    result.unwrap_or(Err(Error::UnparseableResponse(String::from(
+        "no known outcome in the page",
+    ))))
+}
+
+/// Shortens a response body for tracing.
+fn truncate_body(body: &str) -> String {
+    const MAX_CHARS: usize = 200;
+    let body = body.trim();
+    match body.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}... ({} bytes)", &body[..end], body.len()),
+        None => String::from(body),
+    }
+}
+
+/// Translates the status of a response to the request for the path into an Error, or
+/// returns the response body if the request was successful.
+fn check_status(status: StatusCode, path: String, body: String) -> Result<String, Error> {
+    match status {
+        StatusCode::BAD_REQUEST => Err(Error::SessionExpired),
+        StatusCode::NOT_FOUND => Err(Error::NotUnlocked(path)),
+        status if status.is_server_error() => Err(Error::ServerFailure(status)),
+        status if !status.is_success() => Err(Error::UnexpectedStatus(status)),
+        _ => Ok(body),
+    }
+}
+
+/// Refuses requests for puzzles that do not exist or are not unlocked yet without
+/// contacting the server.
+fn check_unlocked(year: i16, day: i8) -> Result<(), Error> {
+    match calendar::unlock_time(year, day) {
+        None => Err(Error::NoSuchPuzzle(year, day)),
+        Some(unlock) if unlock > Utc::now() => Err(Error::Locked(unlock)),
+        Some(_) => Ok(()),
     }
-    result
 }
 
 impl AocClient {
-    pub fn new_with_base(base_url: &str) -> Result<AocClient, Error> {
-        // We need to use an Arc here because reqwest::ClientBuilder requires an
-        // Arc<C> of CookieStore:
-        // https://docs.rs/reqwest/latest/reqwest/blocking/struct.ClientBuilder.html
-        let cookie_store = Arc::new(AocSessionCookieStore::new());
-        let client = reqwest::blocking::ClientBuilder::new()
-            .user_agent("aoc18 (https://github.com/hades/aoc25)")
-            .cookie_provider(cookie_store.clone())
-            .build()?;
-        Ok(AocClient {
-            base_url: String::from(base_url),
-            cookie_store,
-            client,
-        })
+    pub fn new_with_base(base_url: &str, options: ClientOptions) -> Result<AocClient, Error> {
+        let builder = options
+            .http
+            .configure(reqwest::blocking::ClientBuilder::new())?;
+        let core = ClientCore::new(base_url, options);
+        let client = builder.cookie_provider(core.cookie_store.clone()).build()?;
+        Ok(AocClient { core, client })
     }
 
-    pub fn new() -> Result<AocClient, Error> {
-        Self::new_with_base("https://adventofcode.com/")
+    pub fn new(options: ClientOptions) -> Result<AocClient, Error> {
+        let mut client = Self::new_with_base(BASE_URL, options)?;
+        client.set_input_cache("inputs");
+        client.set_submission_archive(SubmissionArchive::default());
+        Ok(client)
     }
 
-    pub fn get_puzzle_input(&self, day: i8) -> Result<String, Error> {
-        let url = self.base_url.clone() + format!("2025/day/{0}/input", day).as_str();
-        let response = self.client.get(url).send()?;
-        match response.error_for_status() {
-            Ok(response) => Ok(response.text()?),
-            Err(e) => Err(Error::HttpError(e)),
+    /// Returns the puzzle input for the day, from the input cache if possible.
+    pub fn get_puzzle_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        match self.core.cached_input(year, day) {
+            Some(input) => Ok(input),
+            None => self.fetch_puzzle_input(year, day),
         }
     }
 
+    pub fn is_input_cached(&self, year: i16, day: i8) -> bool {
+        self.core.is_input_cached(year, day)
+    }
+
+    /// Whether the description is cached with both parts.
+    pub fn is_description_cached(&self, year: i16, day: i8) -> bool {
+        self.core.is_description_cached(year, day)
+    }
+
+    /// Downloads the puzzle input for the day, bypassing and then updating the input cache.
+    pub fn fetch_puzzle_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        check_unlocked(year, day)?;
+        let url = self.core.url(&format!("{year}/day/{day}/input"));
+        let input = self.execute(self.client.get(url).build()?)?;
+        self.core.cache_input(year, day, &input)?;
+        Ok(input)
+    }
+
+    /// Downloads the HTML of the puzzle page for the day.
+    pub fn fetch_puzzle_page(&self, year: i16, day: i8) -> Result<String, Error> {
+        check_unlocked(year, day)?;
+        let url = self.core.url(&format!("{year}/day/{day}"));
+        self.execute(self.client.get(url).build()?)
+    }
+
+    /// Returns the puzzle description for the day as Markdown, from the cache if the
+    /// cached copy contains both parts.
+    pub fn get_puzzle_description(&self, year: i16, day: i8) -> Result<String, Error> {
+        match self.core.cached_description(year, day) {
+            Some(description) => Ok(description),
+            None => self.fetch_puzzle_description(year, day),
+        }
+    }
+
+    /// Downloads the puzzle description for the day, bypassing and then updating the cache.
+    pub fn fetch_puzzle_description(&self, year: i16, day: i8) -> Result<String, Error> {
+        let description = page::day_descriptions(&self.fetch_puzzle_page(year, day)?).join("\n");
+        self.core.cache_description(year, day, &description)?;
+        Ok(description)
+    }
+
+    /// Downloads the puzzle page for the day and extracts the examples from it.
+    pub fn fetch_puzzle_examples(&self, year: i16, day: i8) -> Result<PuzzleExamples, Error> {
+        Ok(page::examples(&self.fetch_puzzle_page(year, day)?))
+    }
+
+    pub fn get_private_leaderboard(&self, year: i16, id: u64) -> Result<Leaderboard, Error> {
+        let url = self
+            .core
+            .url(&format!("{year}/leaderboard/private/view/{id}.json"));
+        Ok(serde_json::from_str(
+            &self.execute(self.client.get(url).build()?)?,
+        )?)
+    }
+
+    /// Returns the answers accepted for the solved parts of the day, from the day page.
+    pub fn fetch_accepted_answers(&self, year: i16, day: i8) -> Result<Vec<String>, Error> {
+        Ok(page::accepted_answers(&self.fetch_puzzle_page(year, day)?))
+    }
+
+    /// Returns the number of stars earned on each unlocked day, from the event calendar.
+    pub fn fetch_stars(&self, year: i16) -> Result<BTreeMap<i8, u8>, Error> {
+        let url = self.core.url(&year.to_string());
+        Ok(page::calendar_stars(
+            &self.execute(self.client.get(url).build()?)?,
+        ))
+    }
+
+    /// Returns the name of the user the session cookie belongs to, or
+    /// `Error::SessionExpired` if the site treats the request as logged out.
+    pub fn whoami(&self, year: i16) -> Result<String, Error> {
+        let url = self.core.url(&format!("{year}/about"));
+        page::user_name(&self.execute(self.client.get(url).build()?)?).ok_or(Error::SessionExpired)
+    }
+
     pub fn submit_answer(
         &self,
+        year: i16,
         day: i8,
         level: i8,
         answer: &str,
     ) -> Result<ValidationResult, Error> {
-        let url = self.base_url.clone() + format!("2025/day/{0}/answer", day).as_str();
+        check_unlocked(year, day)?;
+        let url = self.core.url(&format!("{year}/day/{day}/answer"));
         let request = self
             .client
             .post(url)
-            .form(&HashMap::from([
-                ("level", level.to_string()),
-                ("answer", answer.to_string()),
-            ]))
+            .form(&[("level", level.to_string()), ("answer", answer.to_string())])
             .build()?;
-        let response = self.client.execute(request)?;
-        match response.error_for_status() {
-            Ok(response) => Ok(parse_validation_response(response.text()?.as_str())),
-            Err(e) => Err(Error::HttpError(e)),
+        parse_validation_response(&self.execute(request)?)
+    }
+
+    /// Sends the request and returns the body of the response, translating error
+    /// statuses into the corresponding variants of Error. GET requests that fail with
+    /// transient errors are retried according to the retry policy.
+    fn execute(&self, request: reqwest::blocking::Request) -> Result<String, Error> {
+        let mut attempt = 1;
+        while self.core.may_retry(request.method(), attempt) {
+            let attempt_request = request.try_clone().expect("GET requests can be cloned");
+            match self.execute_once(attempt_request) {
+                Err(e) => match self.core.retry_backoff(request.url().path(), attempt, &e) {
+                    Some(backoff) => sleep(backoff),
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+            attempt += 1;
         }
+        self.execute_once(request)
+    }
+
+    fn execute_once(&self, request: reqwest::blocking::Request) -> Result<String, Error> {
+        let summary = RequestSummary::new(
+            request.method(),
+            request.url(),
+            request.body().and_then(|body| body.as_bytes()),
+        );
+        if let Some(result) = self.core.replay(&summary) {
+            return result;
+        }
+        self.core.check_online(&summary)?;
+        if let Some(throttle) = &self.core.throttle {
+            throttle.wait(request.method() == Method::POST)?;
+        }
+        let start = Instant::now();
+        let response = self.client.execute(request).inspect_err(|e| {
+            self.core.trace_failure(&summary, e, start.elapsed());
+        })?;
+        let status = response.status();
+        let text = response.text()?;
+        self.core.finish(summary, status, text, start.elapsed())
     }
 
     pub fn set_cookie(&self, cookie: &str) {
-        *self.cookie_store.cookie.write().unwrap() = Some(String::from(cookie));
+        self.core.set_cookie(cookie);
+    }
+
+    pub fn set_input_cache(&mut self, dir: impl Into<PathBuf>) {
+        self.core.input_cache = Some(InputCache::new(dir));
+    }
+
+    pub fn set_submission_archive(&mut self, archive: SubmissionArchive) {
+        self.core.archive = Some(archive);
     }
 }
 
@@ -0,0 +1,132 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The response AoC sent to an answer submission, kept verbatim.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedResponse {
+    /// RFC 3339 time of the submission, in UTC.
+    pub submitted_at: String,
+    pub year: i16,
+    pub day: i8,
+    pub level: i8,
+    pub answer: String,
+    /// HTTP status of the response.
+    pub status: u16,
+    pub body: String,
+}
+
+impl ArchivedResponse {
+    pub fn new(
+        year: i16,
+        day: i8,
+        level: i8,
+        answer: &str,
+        status: u16,
+        body: &str,
+    ) -> ArchivedResponse {
+        ArchivedResponse {
+            submitted_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            year,
+            day,
+            level,
+            answer: String::from(answer),
+            status,
+            body: String::from(body),
+        }
+    }
+}
+
+/// Directory of submission responses, laid out as
+/// `<root>/<year>/dayNN-partN-<time>.toml`.
+#[derive(Clone)]
+pub struct SubmissionArchive {
+    root: PathBuf,
+}
+
+impl Default for SubmissionArchive {
+    fn default() -> SubmissionArchive {
+        SubmissionArchive::new("submissions")
+    }
+}
+
+impl SubmissionArchive {
+    pub fn new(root: impl Into<PathBuf>) -> SubmissionArchive {
+        SubmissionArchive { root: root.into() }
+    }
+
+    pub fn save(&self, response: &ArchivedResponse) -> io::Result<PathBuf> {
+        let dir = self.root.join(response.year.to_string());
+        fs::create_dir_all(&dir)?;
+        let time: String = response
+            .submitted_at
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '.')
+            .collect();
+        let path = dir.join(format!(
+            "day{:02}-part{}-{time}.toml",
+            response.day, response.level
+        ));
+        fs::write(
+            &path,
+            toml::to_string(response).expect("serializing archived response"),
+        )?;
+        Ok(path)
+    }
+
+    /// Returns all archived responses, oldest first.
+    pub fn list(&self) -> io::Result<Vec<ArchivedResponse>> {
+        let years = match fs::read_dir(&self.root) {
+            Ok(years) => years,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        let mut responses = vec![];
+        for year in years {
+            let year = year?.path();
+            if !year.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(year)? {
+                let path = file?.path();
+                if path.extension().is_none_or(|extension| extension != "toml") {
+                    continue;
+                }
+                match toml::from_str(&fs::read_to_string(&path)?) {
+                    Ok(response) => responses.push(response),
+                    Err(e) => log::warn!("skipping unreadable {}: {e}", path.display()),
+                }
+            }
+        }
+        responses.sort_by(|a: &ArchivedResponse, b| a.submitted_at.cmp(&b.submitted_at));
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_saved_responses_in_order() {
+        let dir = tempfile::tempdir().expect("creating archive directory");
+        let archive = SubmissionArchive::new(dir.path());
+        assert_eq!(archive.list().expect("listing empty archive"), vec![]);
+        let mut first = ArchivedResponse::new(2025, 3, 1, "41", 200, "<article>low</article>");
+        first.submitted_at = String::from("2025-12-03T05:00:01.000Z");
+        let mut second = ArchivedResponse::new(2024, 3, 1, "42", 200, "<article>right</article>");
+        second.submitted_at = String::from("2025-12-03T05:01:00.000Z");
+        let path = archive.save(&second).expect("saving response");
+        assert!(path.ends_with("2024/day03-part1-20251203T050100.000Z.toml"));
+        archive.save(&first).expect("saving response");
+        assert_eq!(
+            archive.list().expect("listing archive"),
+            vec![first, second]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// On-disk cache of puzzle inputs and descriptions, laid out as
+/// `<root>/<year>/dayNN.txt` and `<root>/<year>/dayNN.md`.
+#[derive(Clone)]
+pub struct InputCache {
+    root: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<PathBuf>) -> InputCache {
+        InputCache { root: root.into() }
+    }
+
+    fn path(&self, year: i16, day: i8, extension: &str) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{day:02}.{extension}"))
+    }
+
+    fn read(&self, year: i16, day: i8, extension: &str) -> Option<String> {
+        fs::read_to_string(self.path(year, day, extension)).ok()
+    }
+
+    fn write(&self, year: i16, day: i8, extension: &str, contents: &str) -> io::Result<()> {
+        let path = self.path(year, day, extension);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+
+    pub fn input_path(&self, year: i16, day: i8) -> PathBuf {
+        self.path(year, day, "txt")
+    }
+
+    /// Returns the cached input for the day, or None if it has not been downloaded yet.
+    pub fn read_input(&self, year: i16, day: i8) -> Option<String> {
+        self.read(year, day, "txt")
+    }
+
+    pub fn write_input(&self, year: i16, day: i8, input: &str) -> io::Result<()> {
+        self.write(year, day, "txt", input)
+    }
+
+    pub fn description_path(&self, year: i16, day: i8) -> PathBuf {
+        self.path(year, day, "md")
+    }
+
+    pub fn read_description(&self, year: i16, day: i8) -> Option<String> {
+        self.read(year, day, "md")
+    }
+
+    pub fn write_description(&self, year: i16, day: i8, description: &str) -> io::Result<()> {
+        self.write(year, day, "md", description)
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// A private leaderboard as served by `/<year>/leaderboard/private/view/<id>.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    /// Unix timestamp of the unlock of the first puzzle of the event.
+    pub day1_ts: i64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// None for anonymous users.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub last_star_ts: i64,
+    /// Stars obtained by the member, keyed by day and then by part.
+    pub completion_day_level: BTreeMap<i8, BTreeMap<i8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of the moment the star was obtained.
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    /// Unix timestamp of the unlock of the given day's puzzle.
+    pub fn unlock_ts(&self, day: i8) -> i64 {
+        self.day1_ts + (day as i64 - 1) * 24 * 3600
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star(&self, day: i8, part: i8) -> Option<&Star> {
+        self.completion_day_level.get(&day)?.get(&part)
+    }
+}
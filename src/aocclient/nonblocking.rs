@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use http::Method;
+
+use super::BASE_URL;
+use super::ClientCore;
+use super::ClientOptions;
+use super::Error;
+use super::Leaderboard;
+use super::PuzzleExamples;
+use super::RequestSummary;
+use super::SubmissionArchive;
+use super::ValidationResult;
+use super::cache::InputCache;
+use super::check_unlocked;
+use super::page;
+use super::parse_validation_response;
+
+/// Async counterpart of the blocking AocClient, for use from a Tokio runtime. Caching,
+/// throttling, retries and recording behave the same way, with the file system work
+/// done on the blocking thread pool.
+pub struct AocClient {
+    core: Arc<ClientCore>,
+    client: reqwest::Client,
+}
+
+impl AocClient {
+    pub fn new_with_base(base_url: &str, options: ClientOptions) -> Result<AocClient, Error> {
+        let builder = options.http.configure(reqwest::ClientBuilder::new())?;
+        let core = ClientCore::new(base_url, options);
+        let client = builder.cookie_provider(core.cookie_store.clone()).build()?;
+        Ok(AocClient {
+            core: Arc::new(core),
+            client,
+        })
+    }
+
+    pub fn new(options: ClientOptions) -> Result<AocClient, Error> {
+        let mut client = Self::new_with_base(BASE_URL, options)?;
+        client.set_input_cache("inputs");
+        client.set_submission_archive(SubmissionArchive::default());
+        Ok(client)
+    }
+
+    /// Returns the puzzle input for the day, from the input cache if possible.
+    pub async fn get_puzzle_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        match self
+            .blocking(move |core| core.cached_input(year, day))
+            .await?
+        {
+            Some(input) => Ok(input),
+            None => self.fetch_puzzle_input(year, day).await,
+        }
+    }
+
+    pub async fn is_input_cached(&self, year: i16, day: i8) -> Result<bool, Error> {
+        self.blocking(move |core| core.is_input_cached(year, day))
+            .await
+    }
+
+    /// Whether the description is cached with both parts.
+    pub async fn is_description_cached(&self, year: i16, day: i8) -> Result<bool, Error> {
+        self.blocking(move |core| core.is_description_cached(year, day))
+            .await
+    }
+
+    /// Downloads the puzzle input for the day, bypassing and then updating the input cache.
+    pub async fn fetch_puzzle_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        check_unlocked(year, day)?;
+        let url = self.core.url(&format!("{year}/day/{day}/input"));
+        let input = self.execute(self.client.get(url).build()?).await?;
+        let cached = input.clone();
+        self.blocking(move |core| core.cache_input(year, day, &cached))
+            .await??;
+        Ok(input)
+    }
+
+    /// Downloads the HTML of the puzzle page for the day.
+    pub async fn fetch_puzzle_page(&self, year: i16, day: i8) -> Result<String, Error> {
+        check_unlocked(year, day)?;
+        let url = self.core.url(&format!("{year}/day/{day}"));
+        self.execute(self.client.get(url).build()?).await
+    }
+
+    /// Returns the puzzle description for the day as Markdown, from the cache if the
+    /// cached copy contains both parts.
+    pub async fn get_puzzle_description(&self, year: i16, day: i8) -> Result<String, Error> {
+        match self
+            .blocking(move |core| core.cached_description(year, day))
+            .await?
+        {
+            Some(description) => Ok(description),
+            None => self.fetch_puzzle_description(year, day).await,
+        }
+    }
+
+    /// Downloads the puzzle description for the day, bypassing and then updating the cache.
+    pub async fn fetch_puzzle_description(&self, year: i16, day: i8) -> Result<String, Error> {
+        let page = self.fetch_puzzle_page(year, day).await?;
+        let description = page::day_descriptions(&page).join("\n");
+        let cached = description.clone();
+        self.blocking(move |core| core.cache_description(year, day, &cached))
+            .await??;
+        Ok(description)
+    }
+
+    /// Downloads the puzzle page for the day and extracts the examples from it.
+    pub async fn fetch_puzzle_examples(&self, year: i16, day: i8) -> Result<PuzzleExamples, Error> {
+        Ok(page::examples(&self.fetch_puzzle_page(year, day).await?))
+    }
+
+    pub async fn get_private_leaderboard(&self, year: i16, id: u64) -> Result<Leaderboard, Error> {
+        let url = self
+            .core
+            .url(&format!("{year}/leaderboard/private/view/{id}.json"));
+        Ok(serde_json::from_str(
+            &self.execute(self.client.get(url).build()?).await?,
+        )?)
+    }
+
+    /// Returns the answers accepted for the solved parts of the day, from the day page.
+    pub async fn fetch_accepted_answers(&self, year: i16, day: i8) -> Result<Vec<String>, Error> {
+        Ok(page::accepted_answers(
+            &self.fetch_puzzle_page(year, day).await?,
+        ))
+    }
+
+    /// Returns the number of stars earned on each unlocked day, from the event calendar.
+    pub async fn fetch_stars(&self, year: i16) -> Result<BTreeMap<i8, u8>, Error> {
+        let url = self.core.url(&year.to_string());
+        Ok(page::calendar_stars(
+            &self.execute(self.client.get(url).build()?).await?,
+        ))
+    }
+
+    /// Returns the name of the user the session cookie belongs to, or
+    /// `Error::SessionExpired` if the site treats the request as logged out.
+    pub async fn whoami(&self, year: i16) -> Result<String, Error> {
+        let url = self.core.url(&format!("{year}/about"));
+        page::user_name(&self.execute(self.client.get(url).build()?).await?)
+            .ok_or(Error::SessionExpired)
+    }
+
+    pub async fn submit_answer(
+        &self,
+        year: i16,
+        day: i8,
+        level: i8,
+        answer: &str,
+    ) -> Result<ValidationResult, Error> {
+        check_unlocked(year, day)?;
+        let url = self.core.url(&format!("{year}/day/{day}/answer"));
+        let request = self
+            .client
+            .post(url)
+            .form(&[("level", level.to_string()), ("answer", answer.to_string())])
+            .build()?;
+        parse_validation_response(&self.execute(request).await?)
+    }
+
+    /// Sends the request and returns the body of the response, retrying GET requests
+    /// like the blocking client does.
+    async fn execute(&self, request: reqwest::Request) -> Result<String, Error> {
+        let mut attempt = 1;
+        while self.core.may_retry(request.method(), attempt) {
+            let attempt_request = request.try_clone().expect("GET requests can be cloned");
+            match self.execute_once(attempt_request).await {
+                Err(e) => match self.core.retry_backoff(request.url().path(), attempt, &e) {
+                    Some(backoff) => tokio::time::sleep(backoff).await,
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+            attempt += 1;
+        }
+        self.execute_once(request).await
+    }
+
+    async fn execute_once(&self, request: reqwest::Request) -> Result<String, Error> {
+        let summary = RequestSummary::new(
+            request.method(),
+            request.url(),
+            request.body().and_then(|body| body.as_bytes()),
+        );
+        let (summary, replayed) = self
+            .blocking(move |core| {
+                let replayed = core.replay(&summary);
+                (summary, replayed)
+            })
+            .await?;
+        if let Some(result) = replayed {
+            return result;
+        }
+        self.core.check_online(&summary)?;
+        if let Some(throttle) = &self.core.throttle {
+            throttle
+                .wait_async(request.method() == Method::POST)
+                .await?;
+        }
+        let start = Instant::now();
+        let response = self.client.execute(request).await.inspect_err(|e| {
+            self.core.trace_failure(&summary, e, start.elapsed());
+        })?;
+        let status = response.status();
+        let text = response.text().await?;
+        let elapsed = start.elapsed();
+        self.blocking(move |core| core.finish(summary, status, text, elapsed))
+            .await?
+    }
+
+    /// Runs the function on the blocking thread pool, so that the file system work of
+    /// the core does not stall the runtime.
+    async fn blocking<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&ClientCore) -> T + Send + 'static,
+    {
+        let core = Arc::clone(&self.core);
+        Ok(tokio::task::spawn_blocking(move || f(&core))
+            .await
+            .map_err(io::Error::from)?)
+    }
+
+    pub fn set_cookie(&self, cookie: &str) {
+        self.core.set_cookie(cookie);
+    }
+
+    pub fn set_input_cache(&mut self, dir: impl Into<PathBuf>) {
+        Arc::make_mut(&mut self.core).input_cache = Some(InputCache::new(dir));
+    }
+
+    pub fn set_submission_archive(&mut self, archive: SubmissionArchive) {
+        Arc::make_mut(&mut self.core).archive = Some(archive);
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use scraper::ElementRef;
+use scraper::Html;
+use scraper::Node;
+use scraper::Selector;
+
+static DAY_DESC: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("article.day-desc").expect("day description selector"));
+static EXAMPLE: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("pre > code").expect("example selector"));
+static CALENDAR_DAY: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("a[class*=calendar-day]").expect("calendar day selector"));
+static ARTICLE: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("main article").expect("article selector"));
+static PARAGRAPH: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("main p").expect("paragraph selector"));
+static CODE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("code").expect("code selector"));
+static USER: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("header div.user").expect("user selector"));
+static HIGHLIGHTED_CODE: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("code > em").expect("highlighted code selector"));
+
+/// Example inputs and answers found in the puzzle description.
+#[derive(Debug, Default, PartialEq)]
+pub struct PuzzleExamples {
+    /// Contents of every code block, in the order they appear on the page.
+    pub inputs: Vec<String>,
+    /// The answer for the examples of each part, taken to be the last highlighted
+    /// code fragment in the description of that part.
+    pub answers: Vec<Option<String>>,
+}
+
+/// Returns the description of each part of the puzzle that is visible on the day page,
+/// converted to Markdown.
+pub fn day_descriptions(html: &str) -> Vec<String> {
+    Html::parse_document(html)
+        .select(&DAY_DESC)
+        .map(|article| {
+            let mut markdown = String::new();
+            write_blocks(&mut markdown, article);
+            markdown.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+pub fn examples(html: &str) -> PuzzleExamples {
+    let document = Html::parse_document(html);
+    let mut examples = PuzzleExamples::default();
+    for article in document.select(&DAY_DESC) {
+        examples
+            .inputs
+            .extend(article.select(&EXAMPLE).map(|code| code.text().collect()));
+        examples.answers.push(
+            article
+                .select(&HIGHLIGHTED_CODE)
+                .last()
+                .map(|em| em.text().collect()),
+        );
+    }
+    examples
+}
+
+/// Returns the accepted answers shown on the day page for the solved parts, in order.
+pub fn accepted_answers(html: &str) -> Vec<String> {
+    Html::parse_document(html)
+        .select(&PARAGRAPH)
+        .filter(|p| {
+            p.text()
+                .collect::<String>()
+                .starts_with("Your puzzle answer was")
+        })
+        .filter_map(|p| p.select(&CODE).next().map(|code| code.text().collect()))
+        .collect()
+}
+
+/// Returns the text of the first article on the page with whitespace collapsed, which
+/// on the answer page is AoC's verdict on the submission.
+pub fn article_text(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let article = document.select(&ARTICLE).next()?;
+    let text: String = article.text().collect();
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Returns the name of the logged-in user shown in the page header, or None if the
+/// page was served to a logged-out visitor.
+pub fn user_name(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let user = document.select(&USER).next()?;
+    // The name is followed by the AoC++ badge and the star count.
+    let name = user
+        .children()
+        .find_map(|child| child.value().as_text().map(|text| text.trim().to_string()))
+        .unwrap_or_default();
+    Some(name).filter(|name| !name.is_empty())
+}
+
+/// Returns the number of stars earned on each day linked from the event calendar page.
+/// Days without stars are included with a count of zero.
+pub fn calendar_stars(html: &str) -> BTreeMap<i8, u8> {
+    Html::parse_document(html)
+        .select(&CALENDAR_DAY)
+        .filter_map(|link| {
+            let day = link
+                .value()
+                .attr("href")?
+                .rsplit('/')
+                .next()?
+                .parse()
+                .ok()?;
+            let classes: Vec<&str> = link.value().classes().collect();
+            let stars = if classes.contains(&"calendar-verycomplete") {
+                2
+            } else if classes.contains(&"calendar-complete") {
+                1
+            } else {
+                0
+            };
+            Some((day, stars))
+        })
+        .collect()
+}
+
+fn write_blocks(out: &mut String, element: ElementRef) {
+    for child in element.children() {
+        let Some(child) = ElementRef::wrap(child) else {
+            continue;
+        };
+        match child.value().name() {
+            "h2" => {
+                out.push_str("## ");
+                write_inline(out, child);
+                out.push_str("\n\n");
+            }
+            "pre" => {
+                let code: String = child.text().collect();
+                out.push_str("```\n");
+                out.push_str(&code);
+                if !code.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            "ul" | "ol" => {
+                for item in child.child_elements() {
+                    out.push_str("- ");
+                    write_inline(out, item);
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            _ => {
+                write_inline(out, child);
+                out.push_str("\n\n");
+            }
+        }
+    }
+}
+
+fn write_inline(out: &mut String, element: ElementRef) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => {
+                for c in text.chars() {
+                    if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+                        out.push('\\');
+                    }
+                    out.push(c);
+                }
+            }
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).expect("element node");
+                match child.value().name() {
+                    "em" => {
+                        out.push('*');
+                        write_inline(out, child);
+                        out.push('*');
+                    }
+                    "code" => {
+                        let code: String = child.text().collect();
+                        out.push('`');
+                        out.push_str(&code);
+                        out.push('`');
+                    }
+                    "a" => {
+                        out.push('[');
+                        write_inline(out, child);
+                        out.push_str("](");
+                        out.push_str(child.value().attr("href").unwrap_or_default());
+                        out.push(')');
+                    }
+                    _ => write_inline(out, child),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>The dial starts at <code>50</code>, see <a href="/2025/about">about</a>.</p>
+<pre><code>L68
+R48
+</code></pre>
+<ul><li>Turn <em>left</em>.</li><li>Turn right.</li></ul>
+<p>What is the <em>password</em>?</p>
+</article>
+<p>Your puzzle answer was <code>1078</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Count every click, the answer is <code><em>6</em></code>.</p>
+<pre><code>R1000
+</code></pre>
+<p>Would click <code><em>10</em></code> times.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn counts_calendar_stars() {
+        assert_eq!(
+            calendar_stars(
+                r#"<html><body><main><pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2025/day/1" class="calendar-day1 calendar-verycomplete">Day 1 <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 2, one star" href="/2025/day/2" class="calendar-day2 calendar-complete">Day 2</a>
+<a aria-label="Day 3" href="/2025/day/3" class="calendar-day3">Day 3</a>
+<span aria-hidden="true" class="calendar-day4">Day 4</span>
+</pre></main></body></html>"#
+            ),
+            BTreeMap::from([(1, 2), (2, 1), (3, 0)])
+        );
+    }
+
+    #[test]
+    fn finds_accepted_answers() {
+        assert_eq!(accepted_answers(DAY_PAGE), vec![String::from("1078")]);
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        assert_eq!(
+            article_text(
+                "<html><body><main>\n<article><p>That's not the right answer; your answer is too <em>low</em>.  [<a href=\"/2025/day/3\">Return to Day 3</a>]</p></article>\n</main></body></html>"
+            ),
+            Some(String::from(
+                "That's not the right answer; your answer is too low. [Return to Day 3]"
+            ))
+        );
+        assert_eq!(article_text("<html><body></body></html>"), None);
+    }
+
+    #[test]
+    fn finds_user_name() {
+        assert_eq!(
+            user_name(
+                r#"<html><body><header><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+<div><div class="user">Jane Doe <a href="/2025/support" class="supporter-badge">(AoC++)</a> <span class="star-count">4*</span></div></div>
+</header><main></main></body></html>"#
+            ),
+            Some(String::from("Jane Doe"))
+        );
+        assert_eq!(
+            user_name(
+                r#"<html><body><header><div><p class="user">[<a href="/2025/auth/login">Log In</a>]</p></div></header></body></html>"#
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn converts_descriptions_to_markdown() {
+        assert_eq!(
+            day_descriptions(DAY_PAGE),
+            vec![
+                "## --- Day 1: Secret Entrance ---
+
+The dial starts at `50`, see [about](/2025/about).
+
+```
+L68
+R48
+```
+
+- Turn *left*.
+- Turn right.
+
+What is the *password*?
+",
+                "## --- Part Two ---
+
+Count every click, the answer is `6`.
+
+```
+R1000
+```
+
+Would click `10` times.
+",
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            examples(DAY_PAGE),
+            PuzzleExamples {
+                inputs: vec![String::from("L68\nR48\n"), String::from("R1000\n")],
+                answers: vec![None, Some(String::from("10"))],
+            }
+        );
+    }
+}
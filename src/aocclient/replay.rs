@@ -0,0 +1,75 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Whether the client talks to the server, and whether the traffic is saved or served
+/// from previously saved fixtures.
+#[derive(Clone, Debug, Default)]
+pub enum TrafficMode {
+    #[default]
+    Live,
+    /// Talk to the server and save every exchange in the directory.
+    Record(PathBuf),
+    /// Serve responses from the exchanges saved in the directory, without any network
+    /// traffic. Requests that were not recorded fail.
+    Replay(PathBuf),
+}
+
+/// A recorded request and the response to it. Headers are not recorded, so the session
+/// cookie never ends up in the fixtures.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Exchange {
+    pub method: String,
+    pub path: String,
+    /// Form-encoded request body, empty for GET requests.
+    pub body: String,
+    pub status: u16,
+    pub response: String,
+}
+
+impl Exchange {
+    /// Identifies the request, so that it can be matched against recorded exchanges.
+    pub fn key(method: &str, path: &str, body: &str) -> String {
+        format!("{method} {path} {body}")
+    }
+
+    fn fixture_path(dir: &Path, key: &str) -> PathBuf {
+        let name: String = key
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        dir.join(name + ".toml")
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let key = Self::key(&self.method, &self.path, &self.body);
+        fs::write(
+            Self::fixture_path(dir, &key),
+            toml::to_string(self).expect("serializing exchange"),
+        )
+    }
+
+    /// Finds the recorded exchange for the request identified by the key.
+    pub fn load(dir: &Path, key: &str) -> Option<Exchange> {
+        let exchange: Exchange =
+            toml::from_str(&fs::read_to_string(Self::fixture_path(dir, key)).ok()?).ok()?;
+        // Different requests may map to the same file name.
+        if Self::key(&exchange.method, &exchange.path, &exchange.body) == key {
+            Some(exchange)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// How many times, and how patiently, to retry requests that failed for reasons that
+/// are likely to go away by themselves, such as timeouts and 5xx responses. Only
+/// idempotent requests are retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each further failure.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the given retry (starting with 1), randomised between
+    /// half and all of the exponential backoff so that clients do not retry in lockstep.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        exponential.mul_f64(rand::random_range(0.5..=1.0))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(10),
+        };
+        for (retry, max) in [(1, 2), (2, 4), (3, 8), (4, 10), (9, 10)] {
+            let backoff = policy.backoff(retry);
+            let max = Duration::from_secs(max);
+            assert!(
+                backoff >= max / 2 && backoff <= max,
+                "{backoff:?} for {retry}"
+            );
+        }
+    }
+}
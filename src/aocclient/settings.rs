@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::Error;
+
+/// Settings of the underlying HTTP client.
+#[derive(Clone, Debug)]
+pub struct HttpSettings {
+    /// Limit on the whole request, from connecting until the body has been read.
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// URL of an HTTP(S) proxy for all requests.
+    pub proxy: Option<String>,
+    /// PEM file with certificates to trust in addition to the system ones.
+    pub ca_bundle: Option<PathBuf>,
+    /// Contact address for the user agent, so that the AoC team can reach whoever runs
+    /// the tool if it misbehaves.
+    pub contact: Option<String>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> HttpSettings {
+        HttpSettings {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            proxy: None,
+            ca_bundle: None,
+            contact: None,
+        }
+    }
+}
+
+/// The methods of the blocking and the async reqwest client builders that HttpSettings
+/// uses, so that both clients are configured the same way.
+pub(super) trait ClientBuilder: Sized {
+    fn user_agent(self, user_agent: String) -> Self;
+    fn timeout(self, timeout: Duration) -> Self;
+    fn connect_timeout(self, timeout: Duration) -> Self;
+    fn proxy(self, proxy: reqwest::Proxy) -> Self;
+    fn add_root_certificate(self, certificate: reqwest::Certificate) -> Self;
+}
+
+impl ClientBuilder for reqwest::blocking::ClientBuilder {
+    fn user_agent(self, user_agent: String) -> Self {
+        reqwest::blocking::ClientBuilder::user_agent(self, user_agent)
+    }
+
+    fn timeout(self, timeout: Duration) -> Self {
+        reqwest::blocking::ClientBuilder::timeout(self, timeout)
+    }
+
+    fn connect_timeout(self, timeout: Duration) -> Self {
+        reqwest::blocking::ClientBuilder::connect_timeout(self, timeout)
+    }
+
+    fn proxy(self, proxy: reqwest::Proxy) -> Self {
+        reqwest::blocking::ClientBuilder::proxy(self, proxy)
+    }
+
+    fn add_root_certificate(self, certificate: reqwest::Certificate) -> Self {
+        reqwest::blocking::ClientBuilder::add_root_certificate(self, certificate)
+    }
+}
+
+#[cfg(feature = "async")]
+impl ClientBuilder for reqwest::ClientBuilder {
+    fn user_agent(self, user_agent: String) -> Self {
+        reqwest::ClientBuilder::user_agent(self, user_agent)
+    }
+
+    fn timeout(self, timeout: Duration) -> Self {
+        reqwest::ClientBuilder::timeout(self, timeout)
+    }
+
+    fn connect_timeout(self, timeout: Duration) -> Self {
+        reqwest::ClientBuilder::connect_timeout(self, timeout)
+    }
+
+    fn proxy(self, proxy: reqwest::Proxy) -> Self {
+        reqwest::ClientBuilder::proxy(self, proxy)
+    }
+
+    fn add_root_certificate(self, certificate: reqwest::Certificate) -> Self {
+        reqwest::ClientBuilder::add_root_certificate(self, certificate)
+    }
+}
+
+impl HttpSettings {
+    /// Applies the settings to a client builder.
+    pub(super) fn configure<B: ClientBuilder>(&self, builder: B) -> Result<B, Error> {
+        let mut builder = builder
+            .user_agent(self.user_agent())
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout);
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for certificate in self.root_certificates()? {
+            builder = builder.add_root_certificate(certificate);
+        }
+        Ok(builder)
+    }
+
+    pub fn user_agent(&self) -> String {
+        let repository = "https://github.com/hades/aoc25";
+        match &self.contact {
+            Some(contact) => format!("aoc25 ({repository} by {contact})"),
+            None => format!("aoc25 ({repository})"),
+        }
+    }
+
+    pub fn proxy(&self) -> Result<Option<reqwest::Proxy>, Error> {
+        Ok(self.proxy.as_deref().map(reqwest::Proxy::all).transpose()?)
+    }
+
+    pub fn root_certificates(&self) -> Result<Vec<reqwest::Certificate>, Error> {
+        match &self.ca_bundle {
+            Some(path) => Ok(reqwest::Certificate::from_pem_bundle(&fs::read(path)?)?),
+            None => Ok(vec![]),
+        }
+    }
+}
@@ -1,104 +1,171 @@
-use httptest::Expectation;
-use httptest::Server;
-use httptest::ServerPool;
-use httptest::matchers::all_of;
-use httptest::matchers::contains;
-use httptest::matchers::matches;
-use httptest::matchers::request;
-use httptest::matchers::url_decoded;
-use httptest::responders::status_code;
+//! The suite in tests/suite.rs runs against the blocking client and, with the `async`
+//! feature, against the async client driven through a blocking adapter. Tests that do
+//! not need a client are at the top level, so that they run once.
 
-use super::*;
+use std::time::Duration;
 
-static SERVER_POOL: ServerPool = ServerPool::new(2);
+use http::Method;
+use http::StatusCode;
+use reqwest::Url;
 
-fn server_url(server: &Server) -> String {
-    let url = server.url("/");
-    let scheme = url.scheme().unwrap();
-    let authority = url.authority().unwrap();
-    format!("{scheme}://{authority}/")
-}
+use super::*;
 
 #[test]
-fn test_get_input_success() {
-    let server = SERVER_POOL.get_server();
-    let m = all_of![
-        request::method("GET"),
-        request::path(matches("/2025/day/19/input")),
-        request::headers(contains(("cookie", "session=deadbeef"))),
-    ];
-    server.expect(Expectation::matching(m).respond_with(status_code(200)));
-    let client =
-        AocClient::new_with_base(server_url(&server).as_str()).expect("creating AoC client");
-    client.set_cookie("deadbeef");
-    assert_eq!(client.get_puzzle_input(19).expect("getting input"), "");
-}
+fn test_cookie_store_hides_cookie() {
+    use reqwest::cookie::CookieStore;
 
-fn submit_answer_test(body: &'static str, expected_result: ValidationResult) {
-    let server = SERVER_POOL.get_server();
-    let m = all_of![
-        request::method("POST"),
-        request::path(matches("/2025/day/19/answer")),
-        request::headers(contains(("cookie", "session=deadbeef"))),
-        request::body(url_decoded(contains(("level", "1")))),
-        request::body(url_decoded(contains(("answer", "THE ANSWER")))),
-    ];
-    server.expect(Expectation::matching(m).respond_with(status_code(200).body(body)));
-    let client =
-        AocClient::new_with_base(server_url(&server).as_str()).expect("creating AoC client");
-    client.set_cookie("deadbeef");
+    let store = AocSessionCookieStore::new();
     assert_eq!(
-        client
-            .submit_answer(19, 1, "THE ANSWER")
-            .expect("getting input"),
-        expected_result
+        format!("{store:?}"),
+        "AocSessionCookieStore { cookie: None }"
     );
-}
-
-#[test]
-fn test_submit_answer_success() {
-    submit_answer_test(
-        "<html><p>That\'s the right answer</p></html>",
-        ValidationResult::Accepted,
+    *store.cookie.write().unwrap() = Some(String::from("deadbeef"));
+    assert_eq!(
+        format!("{store:?}"),
+        "AocSessionCookieStore { cookie: Some(\"<redacted>\") }"
     );
+    let header = store
+        .cookies(&Url::parse("https://adventofcode.com/").unwrap())
+        .expect("cookie header");
+    assert!(header.is_sensitive());
+    assert!(!format!("{header:?}").contains("deadbeef"));
 }
 
 #[test]
-fn test_submit_answer_rejected() {
-    submit_answer_test(
-        "<html><p>That\'s not the right answer</p></html>",
-        ValidationResult::Rejected,
+fn test_truncate_body() {
+    assert_eq!(truncate_body("  short\n"), "short");
+    let long = "é".repeat(250);
+    assert_eq!(
+        truncate_body(&long),
+        format!("{}... (500 bytes)", "é".repeat(200))
     );
 }
 
 #[test]
-fn test_submit_answer_rejected_too_low() {
-    submit_answer_test(
-        "<html><p>That\'s not the right answer - your answer is too low.</p></html>",
-        ValidationResult::RejectedTooLow,
+fn test_trace_line_is_redacted() {
+    let core = ClientCore::new("http://localhost/", ClientOptions::default());
+    core.set_cookie("deadbeef");
+    let url = Url::parse("http://localhost/2025/day/19/input").unwrap();
+    let request = RequestSummary::new(&Method::GET, &url, None);
+    assert_eq!(
+        core.trace_line(
+            &request,
+            StatusCode::OK,
+            "echo session=deadbeef",
+            Duration::from_millis(5)
+        ),
+        "GET http://localhost/2025/day/19/input -> 200 OK in 5ms: echo session=<redacted>"
     );
 }
 
-#[test]
-fn test_submit_answer_rejected_too_high() {
-    submit_answer_test(
-        "<html><p>That\'s not the right answer - your answer is too high.</p></html>",
-        ValidationResult::RejectedTooHigh,
-    );
-}
+mod blocking {
+    use crate::aocclient::AocClient;
 
-#[test]
-fn test_submit_answer_throttled() {
-    submit_answer_test(
-        "<html><p>You gave an answer too recently. You have 57s left to wait</p></html>",
-        ValidationResult::Throttled(Duration::from_secs(57)),
-    );
+    include!("tests/suite.rs");
 }
 
-#[test]
-fn test_submit_answer_throttled_with_minutes() {
-    submit_answer_test(
-        "<html><p>You gave an answer too recently. You have 5m 31s left to wait</p></html>",
-        ValidationResult::Throttled(Duration::from_secs(331)),
-    );
+#[cfg(feature = "async")]
+mod nonblocking {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use tokio::runtime::Runtime;
+
+    use crate::aocclient::ClientOptions;
+    use crate::aocclient::Error;
+    use crate::aocclient::Leaderboard;
+    use crate::aocclient::SubmissionArchive;
+    use crate::aocclient::ValidationResult;
+
+    /// Exposes the async client with the signatures of the blocking one.
+    struct AocClient {
+        client: crate::aocclient::nonblocking::AocClient,
+        runtime: Runtime,
+    }
+
+    impl AocClient {
+        fn new_with_base(base_url: &str, options: ClientOptions) -> Result<AocClient, Error> {
+            Ok(AocClient {
+                client: crate::aocclient::nonblocking::AocClient::new_with_base(base_url, options)?,
+                runtime: tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?,
+            })
+        }
+
+        fn set_cookie(&self, cookie: &str) {
+            self.client.set_cookie(cookie);
+        }
+
+        fn set_input_cache(&mut self, dir: impl Into<PathBuf>) {
+            self.client.set_input_cache(dir);
+        }
+
+        fn set_submission_archive(&mut self, archive: SubmissionArchive) {
+            self.client.set_submission_archive(archive);
+        }
+
+        fn is_input_cached(&self, year: i16, day: i8) -> bool {
+            self.runtime
+                .block_on(self.client.is_input_cached(year, day))
+                .expect("checking the input cache")
+        }
+
+        fn is_description_cached(&self, year: i16, day: i8) -> bool {
+            self.runtime
+                .block_on(self.client.is_description_cached(year, day))
+                .expect("checking the description cache")
+        }
+
+        fn get_puzzle_input(&self, year: i16, day: i8) -> Result<String, Error> {
+            self.runtime
+                .block_on(self.client.get_puzzle_input(year, day))
+        }
+
+        fn fetch_puzzle_input(&self, year: i16, day: i8) -> Result<String, Error> {
+            self.runtime
+                .block_on(self.client.fetch_puzzle_input(year, day))
+        }
+
+        fn get_puzzle_description(&self, year: i16, day: i8) -> Result<String, Error> {
+            self.runtime
+                .block_on(self.client.get_puzzle_description(year, day))
+        }
+
+        fn fetch_puzzle_description(&self, year: i16, day: i8) -> Result<String, Error> {
+            self.runtime
+                .block_on(self.client.fetch_puzzle_description(year, day))
+        }
+
+        fn get_private_leaderboard(&self, year: i16, id: u64) -> Result<Leaderboard, Error> {
+            self.runtime
+                .block_on(self.client.get_private_leaderboard(year, id))
+        }
+
+        fn fetch_accepted_answers(&self, year: i16, day: i8) -> Result<Vec<String>, Error> {
+            self.runtime
+                .block_on(self.client.fetch_accepted_answers(year, day))
+        }
+
+        fn fetch_stars(&self, year: i16) -> Result<BTreeMap<i8, u8>, Error> {
+            self.runtime.block_on(self.client.fetch_stars(year))
+        }
+
+        fn whoami(&self, year: i16) -> Result<String, Error> {
+            self.runtime.block_on(self.client.whoami(year))
+        }
+
+        fn submit_answer(
+            &self,
+            year: i16,
+            day: i8,
+            level: i8,
+            answer: &str,
+        ) -> Result<ValidationResult, Error> {
+            self.runtime
+                .block_on(self.client.submit_answer(year, day, level, answer))
+        }
+    }
+
+    include!("tests/suite.rs");
 }
@@ -0,0 +1,806 @@
+use httptest::Expectation;
+use httptest::Server;
+use httptest::ServerPool;
+use httptest::cycle;
+use httptest::matchers::all_of;
+use httptest::matchers::contains;
+use httptest::matchers::matches;
+use httptest::matchers::request;
+use httptest::matchers::url_decoded;
+use httptest::responders::delay_and_then;
+use httptest::responders::status_code;
+
+use crate::aocclient::*;
+
+static SERVER_POOL: ServerPool = ServerPool::new(2);
+
+fn server_url(server: &Server) -> String {
+    let url = server.url("/");
+    let scheme = url.scheme().unwrap();
+    let authority = url.authority().unwrap();
+    format!("{scheme}://{authority}/")
+}
+
+fn new_client_with_options(server: &Server, options: ClientOptions) -> AocClient {
+    AocClient::new_with_base(server_url(server).as_str(), options).expect("creating AoC client")
+}
+
+fn new_client(server: &Server) -> AocClient {
+    new_client_with_options(server, quick_retries(1))
+}
+
+fn quick_retries(max_attempts: u32) -> ClientOptions {
+    ClientOptions {
+        retry: RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        },
+        throttle: None,
+        traffic: TrafficMode::Live,
+        trace_http: false,
+        offline: false,
+        http: HttpSettings::default(),
+    }
+}
+
+#[test]
+fn test_get_input_success() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+        request::headers(contains(("cookie", "session=deadbeef"))),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(200)));
+    let client = new_client(&server);
+    client.set_cookie("deadbeef");
+    assert_eq!(
+        client.get_puzzle_input(2024, 19).expect("getting input"),
+        ""
+    );
+}
+
+#[test]
+fn test_get_input_other_year() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2018/day/3/input")),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(200).body("#1 @ 1,3: 4x4")));
+    let client = new_client(&server);
+    assert_eq!(
+        client.get_puzzle_input(2018, 3).expect("getting input"),
+        "#1 @ 1,3: 4x4"
+    );
+}
+
+fn get_input_error_test(status: u16) -> Error {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(status)));
+    let client = new_client(&server);
+    client
+        .get_puzzle_input(2024, 19)
+        .expect_err("getting input should fail")
+}
+
+#[test]
+fn test_get_input_session_expired() {
+    assert!(matches!(get_input_error_test(400), Error::SessionExpired));
+}
+
+#[test]
+fn test_get_input_not_unlocked() {
+    assert!(
+        matches!(get_input_error_test(404), Error::NotUnlocked(path) if path == "/2024/day/19/input")
+    );
+}
+
+#[test]
+fn test_get_input_server_error() {
+    assert!(matches!(
+        get_input_error_test(503),
+        Error::ServerFailure(StatusCode::SERVICE_UNAVAILABLE)
+    ));
+}
+
+#[test]
+fn test_get_input_retries_transient_errors() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(Expectation::matching(m).times(3).respond_with(cycle![
+        status_code(503),
+        status_code(502),
+        status_code(200).body("finally"),
+    ]));
+    let client = new_client_with_options(&server, quick_retries(3));
+    assert_eq!(
+        client.get_puzzle_input(2024, 19).expect("getting input"),
+        "finally"
+    );
+}
+
+#[test]
+fn test_get_input_gives_up_after_max_attempts() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(
+        Expectation::matching(m)
+            .times(2)
+            .respond_with(status_code(500)),
+    );
+    let client = new_client_with_options(&server, quick_retries(2));
+    assert!(matches!(
+        client.get_puzzle_input(2024, 19),
+        Err(Error::ServerFailure(StatusCode::INTERNAL_SERVER_ERROR))
+    ));
+}
+
+#[test]
+fn test_get_input_does_not_retry_permanent_errors() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(
+        Expectation::matching(m)
+            .times(1)
+            .respond_with(status_code(400)),
+    );
+    let client = new_client_with_options(&server, quick_retries(3));
+    assert!(matches!(
+        client.get_puzzle_input(2024, 19),
+        Err(Error::SessionExpired)
+    ));
+}
+
+#[test]
+fn test_submit_answer_is_not_retried() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("POST"),
+        request::path(matches("/2024/day/19/answer")),
+    ];
+    server.expect(
+        Expectation::matching(m)
+            .times(1)
+            .respond_with(status_code(503)),
+    );
+    let client = new_client_with_options(&server, quick_retries(3));
+    assert!(matches!(
+        client.submit_answer(2024, 19, 1, "42"),
+        Err(Error::ServerFailure(StatusCode::SERVICE_UNAVAILABLE))
+    ));
+}
+
+#[test]
+fn test_requests_are_throttled() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(
+        Expectation::matching(m)
+            .times(2)
+            .respond_with(status_code(200)),
+    );
+    let state_dir = tempfile::tempdir().expect("creating state directory");
+    let client = new_client_with_options(
+        &server,
+        ClientOptions {
+            throttle: Some(ThrottlePolicy {
+                state_file: state_dir.path().join("throttle.toml"),
+                request_interval: Duration::from_millis(300),
+                submission_interval: Duration::ZERO,
+            }),
+            ..quick_retries(1)
+        },
+    );
+    let start = std::time::Instant::now();
+    client.fetch_puzzle_input(2024, 19).expect("getting input");
+    client.fetch_puzzle_input(2024, 19).expect("getting input");
+    assert!(start.elapsed() >= Duration::from_millis(250));
+}
+
+#[test]
+fn test_record_and_replay() {
+    let server = SERVER_POOL.get_server();
+    server.expect(
+        Expectation::matching(request::path(matches("/2024/day/19/input")))
+            .respond_with(status_code(200).body("recorded input")),
+    );
+    server.expect(
+        Expectation::matching(request::path(matches("/2024/day/19/answer"))).respond_with(
+            status_code(200).body("<p>That\'s not the right answer; your answer is too low.</p>"),
+        ),
+    );
+    let fixtures_dir = tempfile::tempdir().expect("creating fixtures directory");
+    let recording_client = new_client_with_options(
+        &server,
+        ClientOptions {
+            traffic: TrafficMode::Record(fixtures_dir.path().to_path_buf()),
+            ..quick_retries(1)
+        },
+    );
+    recording_client.set_cookie("deadbeef");
+    assert_eq!(
+        recording_client
+            .fetch_puzzle_input(2024, 19)
+            .expect("getting input"),
+        "recorded input"
+    );
+    assert_eq!(
+        recording_client
+            .submit_answer(2024, 19, 1, "42")
+            .expect("submitting answer"),
+        ValidationResult::RejectedTooLow
+    );
+    for fixture in std::fs::read_dir(fixtures_dir.path()).expect("listing fixtures") {
+        let contents = std::fs::read_to_string(fixture.unwrap().path()).expect("reading fixture");
+        assert!(!contents.contains("deadbeef"));
+    }
+
+    // The replaying client must not contact the server, which no longer expects requests.
+    let mut replaying_client = new_client_with_options(
+        &server,
+        ClientOptions {
+            traffic: TrafficMode::Replay(fixtures_dir.path().to_path_buf()),
+            ..quick_retries(1)
+        },
+    );
+    let archive_dir = tempfile::tempdir().expect("creating archive directory");
+    replaying_client.set_submission_archive(SubmissionArchive::new(archive_dir.path()));
+    assert_eq!(
+        replaying_client
+            .fetch_puzzle_input(2024, 19)
+            .expect("getting input"),
+        "recorded input"
+    );
+    assert_eq!(
+        replaying_client
+            .submit_answer(2024, 19, 1, "42")
+            .expect("submitting answer"),
+        ValidationResult::RejectedTooLow
+    );
+    assert!(matches!(
+        replaying_client.submit_answer(2024, 19, 1, "43"),
+        Err(Error::ReplayMiss(_))
+    ));
+    // Replayed responses were not sent by AoC now, so they are not archived.
+    assert!(
+        SubmissionArchive::new(archive_dir.path())
+            .list()
+            .expect("listing archive")
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_get_input_locked() {
+    let server = SERVER_POOL.get_server();
+    let client = new_client(&server);
+    assert!(matches!(
+        client.get_puzzle_input(2999, 1),
+        Err(Error::Locked(_))
+    ));
+}
+
+#[test]
+fn test_get_input_no_such_day() {
+    let server = SERVER_POOL.get_server();
+    let client = new_client(&server);
+    assert!(matches!(
+        client.get_puzzle_input(2025, 13),
+        Err(Error::NoSuchPuzzle(2025, 13))
+    ));
+    assert!(matches!(
+        client.submit_answer(2025, 13, 1, "42"),
+        Err(Error::NoSuchPuzzle(2025, 13))
+    ));
+}
+
+#[test]
+fn test_get_input_cached() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(
+        Expectation::matching(m)
+            .times(1)
+            .respond_with(status_code(200).body("1 2 3\n")),
+    );
+    let cache_dir = tempfile::tempdir().expect("creating cache directory");
+    let mut client = new_client(&server);
+    client.set_input_cache(cache_dir.path());
+    assert!(!client.is_input_cached(2024, 19));
+    assert_eq!(
+        client.get_puzzle_input(2024, 19).expect("getting input"),
+        "1 2 3\n"
+    );
+    assert!(client.is_input_cached(2024, 19));
+    assert!(!client.is_description_cached(2024, 19));
+    assert_eq!(
+        client.get_puzzle_input(2024, 19).expect("getting input"),
+        "1 2 3\n"
+    );
+    assert!(cache_dir.path().join("2024").join("day19.txt").exists());
+}
+
+#[test]
+fn test_fetch_input_refreshes_cache() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19/input")),
+    ];
+    server.expect(Expectation::matching(m).times(2).respond_with(cycle![
+        status_code(200).body("stale"),
+        status_code(200).body("fresh"),
+    ]));
+    let cache_dir = tempfile::tempdir().expect("creating cache directory");
+    let mut client = new_client(&server);
+    client.set_input_cache(cache_dir.path());
+    assert_eq!(
+        client.get_puzzle_input(2024, 19).expect("getting input"),
+        "stale"
+    );
+    assert_eq!(
+        client.fetch_puzzle_input(2024, 19).expect("getting input"),
+        "fresh"
+    );
+    assert_eq!(
+        std::fs::read_to_string(cache_dir.path().join("2024").join("day19.txt"))
+            .expect("reading cached input"),
+        "fresh"
+    );
+}
+
+#[test]
+fn test_get_description_cached_after_part_two() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2024/day/19$")),
+    ];
+    server.expect(
+        Expectation::matching(m).times(2).respond_with(status_code(200).body(
+            r#"<main><article class="day-desc"><h2>--- Day 19: Test ---</h2><p>Part one.</p></article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part two.</p></article></main>"#,
+        )),
+    );
+    let cache_dir = tempfile::tempdir().expect("creating cache directory");
+    let mut client = new_client(&server);
+    client.set_input_cache(cache_dir.path());
+    let expected = "## --- Day 19: Test ---\n\nPart one.\n\n## --- Part Two ---\n\nPart two.\n";
+    assert_eq!(
+        client
+            .fetch_puzzle_description(2024, 19)
+            .expect("getting description"),
+        expected
+    );
+    assert_eq!(
+        client
+            .get_puzzle_description(2024, 19)
+            .expect("getting description"),
+        expected
+    );
+    assert_eq!(
+        client
+            .fetch_puzzle_description(2024, 19)
+            .expect("getting description"),
+        expected
+    );
+}
+
+#[test]
+fn test_get_private_leaderboard() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2025/leaderboard/private/view/1001.json")),
+        request::headers(contains(("cookie", "session=deadbeef"))),
+    ];
+    server.expect(
+        Expectation::matching(m)
+            .respond_with(status_code(200).body(include_str!("../testdata/leaderboard.json"))),
+    );
+    let client = new_client(&server);
+    client.set_cookie("deadbeef");
+    let leaderboard = client
+        .get_private_leaderboard(2025, 1001)
+        .expect("getting leaderboard");
+    assert_eq!(leaderboard.day1_ts, 1764565200);
+    assert_eq!(leaderboard.members.len(), 3);
+    let alice = &leaderboard.members["1001"];
+    assert_eq!(alice.display_name(), "Alice");
+    assert_eq!(alice.local_score, 10);
+    assert_eq!(alice.star(1, 2).map(|s| s.get_star_ts), Some(1764566100));
+    assert!(alice.star(2, 2).is_none());
+    assert_eq!(
+        leaderboard.members["1002"].display_name(),
+        "(anonymous user #1002)"
+    );
+}
+
+fn submit_answer_response(body: &'static str) -> Result<ValidationResult, Error> {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("POST"),
+        request::path(matches("/2024/day/19/answer")),
+        request::headers(contains(("cookie", "session=deadbeef"))),
+        request::body(url_decoded(contains(("level", "1")))),
+        request::body(url_decoded(contains(("answer", "THE ANSWER")))),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(200).body(body)));
+    let client = new_client(&server);
+    client.set_cookie("deadbeef");
+    client.submit_answer(2024, 19, 1, "THE ANSWER")
+}
+
+fn submit_answer_test(body: &'static str, expected_result: ValidationResult) {
+    assert_eq!(
+        submit_answer_response(body).expect("submitting answer"),
+        expected_result
+    );
+}
+
+#[test]
+fn test_submit_answer_archives_response() {
+    let server = SERVER_POOL.get_server();
+    let body = "<html><main><article><p>That's not the right answer.</p></article></main></html>";
+    server.expect(
+        Expectation::matching(request::method("POST")).respond_with(status_code(200).body(body)),
+    );
+    let archive_dir = tempfile::tempdir().expect("creating archive directory");
+    let mut client = new_client(&server);
+    client.set_submission_archive(SubmissionArchive::new(archive_dir.path()));
+    client
+        .submit_answer(2024, 19, 2, "THE ANSWER")
+        .expect("submitting answer");
+    let archived = SubmissionArchive::new(archive_dir.path())
+        .list()
+        .expect("listing archive");
+    assert_eq!(archived.len(), 1);
+    assert_eq!(
+        (archived[0].day, archived[0].level, archived[0].answer.as_str()),
+        (19, 2, "THE ANSWER")
+    );
+    assert_eq!(archived[0].body, body);
+}
+
+#[test]
+fn test_submit_answer_archives_failed_response() {
+    let server = SERVER_POOL.get_server();
+    server.expect(
+        Expectation::matching(request::method("POST"))
+            .respond_with(status_code(502).body("<html>Bad Gateway</html>")),
+    );
+    let archive_dir = tempfile::tempdir().expect("creating archive directory");
+    let mut client = new_client(&server);
+    client.set_submission_archive(SubmissionArchive::new(archive_dir.path()));
+    assert!(matches!(
+        client.submit_answer(2024, 19, 1, "a&b=c"),
+        Err(Error::ServerFailure(_))
+    ));
+    let archived = SubmissionArchive::new(archive_dir.path())
+        .list()
+        .expect("listing archive");
+    assert_eq!(archived.len(), 1);
+    assert_eq!(
+        (archived[0].level, archived[0].answer.as_str(), archived[0].status),
+        (1, "a&b=c", 502)
+    );
+    assert_eq!(archived[0].body, "<html>Bad Gateway</html>");
+}
+
+#[test]
+fn test_submit_answer_success() {
+    submit_answer_test(
+        "<html><p>That\'s the right answer</p></html>",
+        ValidationResult::Accepted,
+    );
+}
+
+#[test]
+fn test_submit_answer_rejected() {
+    submit_answer_test(
+        "<html><p>That\'s not the right answer</p></html>",
+        ValidationResult::Rejected,
+    );
+}
+
+#[test]
+fn test_submit_answer_rejected_too_low() {
+    submit_answer_test(
+        "<html><p>That\'s not the right answer - your answer is too low.</p></html>",
+        ValidationResult::RejectedTooLow,
+    );
+}
+
+#[test]
+fn test_submit_answer_rejected_too_high() {
+    submit_answer_test(
+        "<html><p>That\'s not the right answer - your answer is too high.</p></html>",
+        ValidationResult::RejectedTooHigh,
+    );
+}
+
+#[test]
+fn test_submit_answer_throttled() {
+    submit_answer_test(
+        "<html><p>You gave an answer too recently. You have 57s left to wait</p></html>",
+        ValidationResult::Throttled(Duration::from_secs(57)),
+    );
+}
+
+#[test]
+fn test_submit_answer_throttled_with_minutes() {
+    submit_answer_test(
+        "<html><p>You gave an answer too recently. You have 5m 31s left to wait</p></html>",
+        ValidationResult::Throttled(Duration::from_secs(331)),
+    );
+}
+
+#[test]
+fn test_submit_answer_rejected_with_timeout() {
+    submit_answer_test(
+        "<html><p>That\'s not the right answer. If you\'re stuck, make sure you\'re using the full input data. Please wait one minute before trying again.</p></html>",
+        ValidationResult::RejectedWithTimeout(Duration::from_secs(60)),
+    );
+}
+
+#[test]
+fn test_submit_answer_rejected_with_longer_timeout() {
+    submit_answer_test(
+        "<html><p>That\'s not the right answer. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.</p></html>",
+        ValidationResult::RejectedWithTimeout(Duration::from_secs(300)),
+    );
+}
+
+#[test]
+fn test_submit_answer_rejected_too_low_with_timeout() {
+    submit_answer_test(
+        "<html><p>That\'s not the right answer; your answer is too low. Please wait one minute before trying again.</p></html>",
+        ValidationResult::RejectedTooLow,
+    );
+}
+
+#[test]
+fn test_submit_answer_wrong_level() {
+    submit_answer_test(
+        "<html><p>You don\'t seem to be solving the right level.  Did you already complete it?</p></html>",
+        ValidationResult::WrongLevel,
+    );
+}
+
+#[test]
+fn test_submit_answer_unparseable() {
+    assert!(matches!(
+        submit_answer_response("<html><p>Something unexpected</p></html>"),
+        Err(Error::UnparseableResponse(_))
+    ));
+}
+
+#[test]
+fn test_whoami() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::method("GET"),
+        request::path(matches("/2025/about$")),
+        request::headers(contains(("cookie", "session=deadbeef"))),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(200).body(
+        r#"<html><body><header><div><div class="user">Jane Doe <span class="star-count">4*</span></div></div></header></body></html>"#,
+    )));
+    let client = new_client(&server);
+    client.set_cookie("deadbeef");
+    assert_eq!(client.whoami(2025).expect("checking session"), "Jane Doe");
+}
+
+#[test]
+fn test_whoami_logged_out() {
+    let server = SERVER_POOL.get_server();
+    server.expect(
+        Expectation::matching(request::path(matches("/2025/about$"))).respond_with(
+            status_code(200).body(
+                r#"<html><body><header><div><a href="/2025/auth/login">[Log In]</a></div></header></body></html>"#,
+            ),
+        ),
+    );
+    let client = new_client(&server);
+    client.set_cookie("deadbeef");
+    assert!(matches!(client.whoami(2025), Err(Error::SessionExpired)));
+}
+
+#[test]
+fn test_fetch_stars() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![request::method("GET"), request::path(matches("^/2025$")),];
+    server.expect(Expectation::matching(m).respond_with(status_code(200).body(
+        r#"<pre class="calendar"><a href="/2025/day/1" class="calendar-day1 calendar-verycomplete">Day 1</a>
+<a href="/2025/day/2" class="calendar-day2">Day 2</a></pre>"#,
+    )));
+    let client = new_client(&server);
+    assert_eq!(
+        client.fetch_stars(2025).expect("getting stars"),
+        BTreeMap::from([(1, 2), (2, 0)])
+    );
+}
+
+#[test]
+fn test_fetch_accepted_answers() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![request::method("GET"), request::path(matches("^/2025/day/3$")),];
+    server.expect(Expectation::matching(m).respond_with(status_code(200).body(
+        r#"<main><article class="day-desc"><h2>--- Day 3: Test ---</h2></article>
+<p>Your puzzle answer was <code>357</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2></article>
+<p>Your puzzle answer was <code>3121910778619</code>.</p>
+<p class="day-success">Both parts of this puzzle are complete! They provide two gold stars: **</p></main>"#,
+    )));
+    let client = new_client(&server);
+    assert_eq!(
+        client
+            .fetch_accepted_answers(2025, 3)
+            .expect("getting answers"),
+        vec![String::from("357"), String::from("3121910778619")]
+    );
+}
+
+#[test]
+fn test_cookie_is_redacted() {
+    let server = SERVER_POOL.get_server();
+    server.expect(
+        Expectation::matching(request::path(matches("/2024/day/19/input")))
+            .respond_with(status_code(200).body("echo session=deadbeef")),
+    );
+    let fixtures_dir = tempfile::tempdir().expect("creating fixtures directory");
+    let client = new_client_with_options(
+        &server,
+        ClientOptions {
+            traffic: TrafficMode::Record(fixtures_dir.path().to_path_buf()),
+            trace_http: true,
+            ..quick_retries(1)
+        },
+    );
+    client.set_cookie("deadbeef");
+    assert_eq!(
+        client.fetch_puzzle_input(2024, 19).expect("getting input"),
+        "echo session=deadbeef"
+    );
+    let fixture = Exchange::load(fixtures_dir.path(), "GET /2024/day/19/input ")
+        .expect("loading recorded fixture");
+    assert_eq!(fixture.response, "echo session=<redacted>");
+}
+
+#[test]
+fn test_offline_uses_only_cache() {
+    let server = SERVER_POOL.get_server();
+    let cache_dir = tempfile::tempdir().expect("creating cache directory");
+    std::fs::create_dir_all(cache_dir.path().join("2024")).expect("creating cache");
+    std::fs::write(cache_dir.path().join("2024/day19.txt"), "cached input")
+        .expect("writing cached input");
+    let mut client = new_client_with_options(
+        &server,
+        ClientOptions {
+            offline: true,
+            ..quick_retries(1)
+        },
+    );
+    client.set_input_cache(cache_dir.path());
+    assert_eq!(
+        client.get_puzzle_input(2024, 19).expect("getting input"),
+        "cached input"
+    );
+    assert!(matches!(
+        client.get_puzzle_input(2024, 18),
+        Err(Error::Offline(request)) if request == "GET /2024/day/18/input"
+    ));
+    assert!(matches!(
+        client.submit_answer(2024, 19, 1, "42"),
+        Err(Error::Offline(_))
+    ));
+}
+
+#[test]
+fn test_user_agent_has_contact() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::path(matches("/2024/day/19/input")),
+        request::headers(contains((
+            "user-agent",
+            "aoc25 (https://github.com/hades/aoc25 by elf@example.com)"
+        ))),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(200)));
+    let client = new_client_with_options(
+        &server,
+        ClientOptions {
+            http: HttpSettings {
+                contact: Some(String::from("elf@example.com")),
+                ..Default::default()
+            },
+            ..quick_retries(1)
+        },
+    );
+    client.fetch_puzzle_input(2024, 19).expect("getting input");
+}
+
+#[test]
+fn test_request_timeout() {
+    let server = SERVER_POOL.get_server();
+    server.expect(
+        Expectation::matching(request::path(matches("/2024/day/19/input"))).respond_with(
+            delay_and_then(Duration::from_millis(500), status_code(200)),
+        ),
+    );
+    let client = new_client_with_options(
+        &server,
+        ClientOptions {
+            http: HttpSettings {
+                timeout: Duration::from_millis(50),
+                ..Default::default()
+            },
+            ..quick_retries(1)
+        },
+    );
+    assert!(matches!(
+        client.fetch_puzzle_input(2024, 19),
+        Err(Error::Http(e)) if e.is_timeout()
+    ));
+}
+
+#[test]
+fn test_requests_go_through_proxy() {
+    let server = SERVER_POOL.get_server();
+    let m = all_of![
+        request::path(matches("/2024/day/19/input")),
+        request::headers(contains(("host", "adventofcode.invalid"))),
+    ];
+    server.expect(Expectation::matching(m).respond_with(status_code(200).body("proxied")));
+    let client = AocClient::new_with_base(
+        "http://adventofcode.invalid/",
+        ClientOptions {
+            http: HttpSettings {
+                proxy: Some(server_url(&server)),
+                ..Default::default()
+            },
+            ..quick_retries(1)
+        },
+    )
+    .expect("creating AoC client");
+    assert_eq!(
+        client.fetch_puzzle_input(2024, 19).expect("getting input"),
+        "proxied"
+    );
+}
+
+#[test]
+fn test_missing_ca_bundle() {
+    let result = AocClient::new_with_base(
+        "http://adventofcode.invalid/",
+        ClientOptions {
+            http: HttpSettings {
+                ca_bundle: Some(std::path::PathBuf::from("/nonexistent/ca.pem")),
+                ..Default::default()
+            },
+            ..quick_retries(1)
+        },
+    );
+    assert!(matches!(result, Err(Error::Io(_))));
+}
@@ -0,0 +1,233 @@
+use std::ffi::OsString;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use log::info;
+use log::warn;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config;
+
+/// Minimum intervals between outbound requests, enforced across runs of the tool by
+/// keeping the time of the last request in a state file. Runs wait for each other while
+/// holding a lock on the state file, so that concurrent runs do not send at once.
+#[derive(Clone, Debug)]
+pub struct ThrottlePolicy {
+    pub state_file: PathBuf,
+    /// Minimum time between any two requests.
+    pub request_interval: Duration,
+    /// Minimum time between two answer submissions.
+    pub submission_interval: Duration,
+}
+
+impl Default for ThrottlePolicy {
+    fn default() -> ThrottlePolicy {
+        ThrottlePolicy {
+            state_file: config::dir().map_or_else(
+                || PathBuf::from(".aoc-throttle.toml"),
+                |dir| dir.join("throttle.toml"),
+            ),
+            request_interval: Duration::from_secs(5),
+            submission_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ThrottleState {
+    /// Milliseconds since the Unix epoch.
+    last_request: Option<u64>,
+    last_submission: Option<u64>,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time after the Unix epoch")
+        .as_millis() as u64
+}
+
+fn remaining(last: Option<u64>, interval: Duration, now: u64) -> Duration {
+    last.map_or(Duration::ZERO, |last| {
+        interval.saturating_sub(Duration::from_millis(now.saturating_sub(last)))
+    })
+}
+
+impl ThrottlePolicy {
+    /// Returns the path next to the state file with the suffix appended to its name.
+    fn sibling_path(&self, suffix: &str) -> PathBuf {
+        let mut name = self
+            .state_file
+            .file_name()
+            .map(OsString::from)
+            .unwrap_or_default();
+        name.push(suffix);
+        self.state_file.with_file_name(name)
+    }
+
+    /// Takes an exclusive lock that is held until the returned file is dropped.
+    fn lock(&self) -> io::Result<File> {
+        if let Some(parent) = self.state_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let lock = File::create(self.sibling_path(".lock"))?;
+        lock.lock()?;
+        Ok(lock)
+    }
+
+    /// Reads the state. A state file that cannot be parsed is taken to mean that a
+    /// request was just sent, to err on the side of waiting.
+    fn read_state(&self) -> ThrottleState {
+        let state = match fs::read_to_string(&self.state_file) {
+            Ok(state) => state,
+            Err(_) => return ThrottleState::default(),
+        };
+        toml::from_str(&state).unwrap_or_else(|e| {
+            warn!("invalid throttle state {}: {e}", self.state_file.display());
+            let now = now_millis();
+            ThrottleState {
+                last_request: Some(now),
+                last_submission: Some(now),
+            }
+        })
+    }
+
+    /// Returns how long to wait before the next request is allowed.
+    fn delay(&self, submission: bool) -> Duration {
+        let state = self.read_state();
+        let now = now_millis();
+        let mut delay = remaining(state.last_request, self.request_interval, now);
+        if submission {
+            delay = delay.max(remaining(
+                state.last_submission,
+                self.submission_interval,
+                now,
+            ));
+        }
+        if !delay.is_zero() {
+            info!(
+                "delaying {} by {delay:?} to respect the request throttle",
+                if submission { "submission" } else { "request" }
+            );
+        }
+        delay
+    }
+
+    /// Records that a request is being sent now, replacing the state file atomically.
+    fn record(&self, submission: bool) -> io::Result<()> {
+        let mut state = self.read_state();
+        let now = now_millis();
+        state.last_request = Some(now);
+        if submission {
+            state.last_submission = Some(now);
+        }
+        let temp_path = self.sibling_path(".tmp");
+        let mut temp = File::create(&temp_path)?;
+        temp.write_all(
+            toml::to_string(&state)
+                .expect("serializing throttle state")
+                .as_bytes(),
+        )?;
+        temp.sync_all()?;
+        fs::rename(&temp_path, &self.state_file)
+    }
+
+    /// Blocks until the next request is allowed, then records it as sent.
+    pub fn wait(&self, submission: bool) -> io::Result<()> {
+        let _lock = self.lock()?;
+        sleep(self.delay(submission));
+        self.record(submission)
+    }
+
+    /// Like `wait`, but sleeps without blocking the runtime and accesses the state file
+    /// on the blocking thread pool.
+    #[cfg(feature = "async")]
+    pub async fn wait_async(&self, submission: bool) -> io::Result<()> {
+        let policy = self.clone();
+        let (lock, delay) = tokio::task::spawn_blocking(move || {
+            policy.lock().map(|lock| (lock, policy.delay(submission)))
+        })
+        .await??;
+        tokio::time::sleep(delay).await;
+        let policy = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = policy.record(submission);
+            drop(lock);
+            result
+        })
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn policy(dir: &tempfile::TempDir) -> ThrottlePolicy {
+        ThrottlePolicy {
+            state_file: dir.path().join("throttle.toml"),
+            request_interval: Duration::from_millis(200),
+            submission_interval: Duration::from_millis(500),
+        }
+    }
+
+    #[test]
+    fn first_request_is_not_delayed() {
+        let dir = tempfile::tempdir().expect("creating state directory");
+        let start = Instant::now();
+        policy(&dir).wait(true).expect("waiting");
+        assert!(start.elapsed() < Duration::from_millis(200));
+    }
+
+    #[test]
+    fn delay_persists_across_instances() {
+        let dir = tempfile::tempdir().expect("creating state directory");
+        policy(&dir).wait(false).expect("waiting");
+        let start = Instant::now();
+        policy(&dir).wait(false).expect("waiting");
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn submissions_have_separate_interval() {
+        let dir = tempfile::tempdir().expect("creating state directory");
+        policy(&dir).wait(true).expect("waiting");
+        let start = Instant::now();
+        policy(&dir).wait(false).expect("waiting");
+        assert!(start.elapsed() < Duration::from_millis(450));
+        policy(&dir).wait(true).expect("waiting");
+        assert!(start.elapsed() >= Duration::from_millis(450));
+    }
+
+    #[test]
+    fn concurrent_runs_wait_for_each_other() {
+        let dir = tempfile::tempdir().expect("creating state directory");
+        let start = Instant::now();
+        std::thread::scope(|scope| {
+            for _ in 0..2 {
+                scope.spawn(|| policy(&dir).wait(false).expect("waiting"));
+            }
+        });
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn invalid_state_delays_request() {
+        let dir = tempfile::tempdir().expect("creating state directory");
+        fs::write(dir.path().join("throttle.toml"), "last_request = ").expect("writing state");
+        let start = Instant::now();
+        policy(&dir).wait(false).expect("waiting");
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert!(!dir.path().join("throttle.toml.tmp").exists());
+    }
+}
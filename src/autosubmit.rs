@@ -1,15 +1,27 @@
+use crate::aocclient::Error;
 use crate::aocclient::ValidationResult;
+use crate::calendar;
+use crate::source::PuzzleSource;
 
 use std::cmp;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
 use std::thread::sleep;
 
 use log::debug;
+use log::warn;
 use serde::Deserialize;
 use serde::Serialize;
 
-const FILE: &str = "results.toml";
+/// Default location of the submission log.
+pub const FILE: &str = "results.toml";
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 struct PuzzleLogEntry {
@@ -17,6 +29,16 @@ struct PuzzleLogEntry {
     accepted_answer: Option<String>,
     upper_bound: Option<i64>,
     lower_bound: Option<i64>,
+    /// The part was solved according to the event calendar, possibly without the
+    /// accepted answer going through this log.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    completed: bool,
+}
+
+impl PuzzleLogEntry {
+    fn is_solved(&self) -> bool {
+        self.completed || self.accepted_answer.is_some()
+    }
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -24,23 +46,92 @@ struct Log {
     answers: HashMap<String, PuzzleLogEntry>,
 }
 
-fn puzzle_key(day: i8, level: i8) -> String {
-    format!("day{0}part{1}", day, level)
+// Logs written before multiple years were supported have keys without the year.
+const LEGACY_YEAR: i16 = 2025;
+
+fn puzzle_key(year: i16, day: i8, level: i8) -> String {
+    format!("year{0}day{1}part{2}", year, day, level)
+}
+
+fn parse_submission_log(log: &str) -> Result<Log, toml::de::Error> {
+    let mut log: Log = toml::from_str(log)?;
+    log.answers = log
+        .answers
+        .into_iter()
+        .map(|(key, entry)| {
+            if key.starts_with("day") {
+                (format!("year{LEGACY_YEAR}{key}"), entry)
+            } else {
+                (key, entry)
+            }
+        })
+        .collect();
+    Ok(log)
+}
+
+fn read_submission_log(path: &Path) -> Option<Log> {
+    parse_submission_log(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Returns the path next to the submission log with the suffix appended to its name.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_else(|| OsString::from(FILE));
+    name.push(suffix);
+    path.with_file_name(name)
 }
 
-fn read_submission_log() -> Option<Log> {
-    let log = fs::read_to_string(FILE).ok()?;
-    let log: Log = toml::from_str(log.as_str()).ok()?;
-    Some(log)
+/// Replaces the submission log without ever leaving a partly written file behind: the
+/// new version is written to a temporary file that is renamed over the log, and the
+/// previous version is kept in `results.toml.bak`.
+fn write_submission_log(path: &Path, log: &Log) -> io::Result<()> {
+    let contents = toml::to_string_pretty(log).map_err(io::Error::other)?;
+    let temp_path = sibling_path(path, ".tmp");
+    let mut temp = File::create(&temp_path)?;
+    temp.write_all(contents.as_bytes())?;
+    temp.sync_all()?;
+    match fs::copy(path, sibling_path(path, ".bak")) {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    fs::rename(&temp_path, path)
 }
 
-fn write_submission_log(log: &Log) {
-    fs::write(FILE, toml::to_string_pretty(log).unwrap().as_str()).unwrap();
+/// Applies the change to the submission log while holding an exclusive lock on
+/// `results.toml.lock`, so that concurrent runs do not lose each other's entries. The
+/// log is written only if the change returns true. A log that cannot be parsed is left
+/// alone rather than overwritten.
+fn update_submission_log(path: &Path, change: impl FnOnce(&mut Log) -> bool) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock = File::create(sibling_path(path, ".lock"))?;
+    lock.lock()?;
+    let mut log = match fs::read_to_string(path) {
+        Ok(log) => {
+            parse_submission_log(&log).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Log::default(),
+        Err(e) => return Err(e),
+    };
+    if change(&mut log) {
+        write_submission_log(path, &log)?;
+    }
+    Ok(())
 }
 
-fn check_submission_log(day: i8, level: i8, answer: &str) -> Option<ValidationResult> {
-    let log = read_submission_log()?;
-    if let Some(entry) = log.answers.get(puzzle_key(day, level).as_str()) {
+fn check_submission_log(
+    path: &Path,
+    year: i16,
+    day: i8,
+    level: i8,
+    answer: &str,
+) -> Option<ValidationResult> {
+    let log = read_submission_log(path)?;
+    if let Some(entry) = log.answers.get(puzzle_key(year, day, level).as_str()) {
         if let Some(accepted_answer) = &entry.accepted_answer {
             return Some(if answer == accepted_answer {
                 ValidationResult::Accepted
@@ -48,6 +139,10 @@ fn check_submission_log(day: i8, level: i8, answer: &str) -> Option<ValidationRe
                 ValidationResult::Rejected
             });
         }
+        if entry.completed {
+            // The site only answers that the level was already completed.
+            return Some(ValidationResult::WrongLevel);
+        }
         if let Ok(answer_int) = answer.parse::<i64>() {
             if let Some(upper_bound) = entry.upper_bound
                 && answer_int >= upper_bound
@@ -67,92 +162,365 @@ fn check_submission_log(day: i8, level: i8, answer: &str) -> Option<ValidationRe
     None
 }
 
-fn record_submission_log(day: i8, level: i8, answer: &str, result: &ValidationResult) {
-    let mut log = read_submission_log()
-        .or_else(|| Some(Log::default()))
-        .unwrap();
-    let key = puzzle_key(day, level);
-    if !log.answers.contains_key(&key) {
-        log.answers.insert(key.clone(), PuzzleLogEntry::default());
-    }
-    let entry = log.answers.get_mut(&key).unwrap();
-    match *result {
-        ValidationResult::Accepted => {
-            entry.accepted_answer = Some(answer.to_string());
+fn record_submission_log(
+    path: &Path,
+    year: i16,
+    day: i8,
+    level: i8,
+    answer: &str,
+    result: &ValidationResult,
+) -> io::Result<()> {
+    update_submission_log(path, |log| {
+        let key = puzzle_key(year, day, level);
+        if !log.answers.contains_key(&key) {
+            log.answers.insert(key.clone(), PuzzleLogEntry::default());
         }
-        ValidationResult::Rejected => {
-            if !entry.rejected_answers.iter().any(|a| a == answer) {
-                entry.rejected_answers.push(answer.to_string());
+        let entry = log.answers.get_mut(&key).unwrap();
+        match *result {
+            ValidationResult::Accepted => {
+                entry.accepted_answer = Some(answer.to_string());
             }
-        }
-        ValidationResult::RejectedTooLow => {
-            if let Ok(answer_int) = answer.parse::<i64>() {
-                let mut lower_bound = answer_int;
-                if let Some(old_lower_bound) = entry.lower_bound {
-                    lower_bound = cmp::max(lower_bound, old_lower_bound);
+            ValidationResult::Rejected | ValidationResult::RejectedWithTimeout(_) => {
+                if !entry.rejected_answers.iter().any(|a| a == answer) {
+                    entry.rejected_answers.push(answer.to_string());
                 }
-                entry.lower_bound = Some(lower_bound);
             }
-        }
-        ValidationResult::RejectedTooHigh => {
-            if let Ok(answer_int) = answer.parse::<i64>() {
-                let mut upper_bound = answer_int;
-                if let Some(old_upper_bound) = entry.upper_bound {
-                    upper_bound = cmp::min(upper_bound, old_upper_bound);
+            ValidationResult::RejectedTooLow => {
+                if let Ok(answer_int) = answer.parse::<i64>() {
+                    let mut lower_bound = answer_int;
+                    if let Some(old_lower_bound) = entry.lower_bound {
+                        lower_bound = cmp::max(lower_bound, old_lower_bound);
+                    }
+                    entry.lower_bound = Some(lower_bound);
                 }
-                entry.upper_bound = Some(upper_bound);
+            }
+            ValidationResult::RejectedTooHigh => {
+                if let Ok(answer_int) = answer.parse::<i64>() {
+                    let mut upper_bound = answer_int;
+                    if let Some(old_upper_bound) = entry.upper_bound {
+                        upper_bound = cmp::min(upper_bound, old_upper_bound);
+                    }
+                    entry.upper_bound = Some(upper_bound);
+                }
+            }
+            ValidationResult::WrongLevel => {
+                // Says nothing about the answer itself, so there is nothing to record.
+                return false;
+            }
+            ValidationResult::Throttled(_) => {
+                panic!("unexpected Throttled value in record_submission_log");
             }
         }
-        ValidationResult::Throttled(_) => {
-            panic!("unexpected Throttled value in record_submission_log");
-        }
-    }
-    write_submission_log(&log);
+        true
+    })
 }
 
-pub fn submit_with_cache<'a, F>(
+/// Returns the result of submitting the answer, from the submission log if it is known
+/// already, or else by submitting it to the source and recording the result. Errors
+/// from the source are passed through and leave the log unchanged. A failure to record
+/// the result is only logged, as the answer has been submitted by then.
+pub fn submit_with_cache(
+    log_path: &Path,
+    source: &dyn PuzzleSource,
+    year: i16,
     day: i8,
     level: i8,
-    answer: &'a str,
-    mut submit_fn: F,
-) -> ValidationResult
-where
-    F: FnMut(i8, i8, &'a str) -> ValidationResult,
-{
-    if let Some(result) = check_submission_log(day, level, answer) {
-        debug!("answer provided by submission log in results.toml");
-        return result;
+    answer: &str,
+) -> Result<ValidationResult, Error> {
+    if let Some(result) = check_submission_log(log_path, year, day, level, answer) {
+        debug!(
+            "answer provided by submission log in {}",
+            log_path.display()
+        );
+        return Ok(result);
     }
     if answer.is_empty() || answer == "0" {
         debug!("cowardly refusing to submit the answer of {answer}");
-        return ValidationResult::Rejected;
+        return Ok(ValidationResult::Rejected);
     }
     let mut result;
     loop {
-        result = submit_fn(day, level, answer);
+        result = source.submit_answer(year, day, level, answer)?;
         if let ValidationResult::Throttled(timeout) = result {
             sleep(timeout);
         } else {
             break;
         }
     }
-    record_submission_log(day, level, answer, &result);
-    result
+    // The answer has been submitted at this point, so its result must reach the caller
+    // even if it cannot be logged.
+    if let Err(e) = record_submission_log(log_path, year, day, level, answer, &result) {
+        warn!(
+            "failed to record {result:?} for {answer} in {}: {e}",
+            log_path.display()
+        );
+    }
+    Ok(result)
 }
 
-pub fn next_unsolved_day() -> i8 {
+/// Tells whether the part was solved, either by an accepted submission or according to
+/// the event calendar.
+pub fn is_solved(log_path: &Path, year: i16, day: i8, level: i8) -> bool {
+    read_submission_log(log_path)
+        .and_then(|log| {
+            log.answers
+                .get(&puzzle_key(year, day, level))
+                .map(PuzzleLogEntry::is_solved)
+        })
+        .unwrap_or(false)
+}
+
+/// Returns the day after the last one with both parts solved, or None if that was the
+/// last day of the event.
+pub fn next_unsolved_day(log_path: &Path, year: i16) -> Option<i8> {
     let mut last_fully_solved_day = 0;
-    if let Some(submission_log) = read_submission_log() {
-        for day in 1..24 {
+    if let Some(submission_log) = read_submission_log(log_path) {
+        for day in 1..=calendar::days_in_event(year) {
             if let (Some(part1), Some(part2)) = (
-                submission_log.answers.get(puzzle_key(day, 1).as_str()),
-                submission_log.answers.get(puzzle_key(day, 2).as_str()),
-            ) && part1.accepted_answer.is_some()
-                && part2.accepted_answer.is_some()
+                submission_log
+                    .answers
+                    .get(puzzle_key(year, day, 1).as_str()),
+                submission_log
+                    .answers
+                    .get(puzzle_key(year, day, 2).as_str()),
+            ) && part1.is_solved()
+                && part2.is_solved()
             {
                 last_fully_solved_day = day;
             }
         }
     }
-    last_fully_solved_day + 1
+    (last_fully_solved_day < calendar::days_in_event(year)).then_some(last_fully_solved_day + 1)
+}
+
+/// Marks the parts that have stars on the event calendar as completed in the submission
+/// log. Returns the number of parts that were not known to be solved before.
+pub fn reconcile_stars(log_path: &Path, year: i16, stars: &BTreeMap<i8, u8>) -> io::Result<usize> {
+    let mut updated = 0;
+    update_submission_log(log_path, |log| {
+        for (&day, &count) in stars {
+            for level in 1..=cmp::min(count, 2) as i8 {
+                let entry = log.answers.entry(puzzle_key(year, day, level)).or_default();
+                if !entry.is_solved() {
+                    updated += 1;
+                }
+                entry.completed = true;
+            }
+        }
+        updated > 0
+    })?;
+    Ok(updated)
+}
+
+/// Records the answers shown on the day page as accepted, one per part in order.
+/// Returns the number of parts whose accepted answer was not known before.
+pub fn import_accepted_answers(
+    log_path: &Path,
+    year: i16,
+    day: i8,
+    answers: &[String],
+) -> io::Result<usize> {
+    let mut updated = 0;
+    update_submission_log(log_path, |log| {
+        for (level, answer) in (1..).zip(answers) {
+            let entry = log.answers.entry(puzzle_key(year, day, level)).or_default();
+            match &entry.accepted_answer {
+                Some(accepted) if accepted == answer => continue,
+                Some(accepted) => warn!(
+                    "{year} day {day} part {level} was logged as accepting {accepted}, but the site says {answer}"
+                ),
+                None => {}
+            }
+            entry.accepted_answer = Some(answer.clone());
+            updated += 1;
+        }
+        updated > 0
+    })?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use super::*;
+    use crate::aocclient::AocClient;
+    use crate::aocclient::ClientOptions;
+    use crate::aocclient::RetryPolicy;
+    use crate::source::DirectorySource;
+    use aoc25::fakeserver::FakeAoc;
+
+    #[test]
+    fn submits_against_fake_server() {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("starting fake server");
+        let base_url = format!("http://{}/", server.server_addr());
+        let aoc = FakeAoc::new(
+            PathBuf::from("/nonexistent"),
+            HashMap::from([(String::from("2025/3/1"), String::from("42"))]),
+            Duration::from_secs(1),
+        );
+        thread::spawn(move || aoc.serve(&server));
+
+        let client = AocClient::new_with_base(
+            &base_url,
+            ClientOptions {
+                retry: RetryPolicy {
+                    max_attempts: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .expect("creating AoC client");
+        client.set_cookie("deadbeef");
+        let log_dir = tempfile::tempdir().expect("creating log directory");
+        let log_path = log_dir.path().join("results.toml");
+        let submit = |answer| {
+            submit_with_cache(&log_path, &client, 2025, 3, 1, answer).expect("submitting answer")
+        };
+
+        assert_eq!(submit("41"), ValidationResult::RejectedTooLow);
+        // Known to be too low from the log, so not submitted again.
+        assert_eq!(submit("40"), ValidationResult::RejectedTooLow);
+        // Submitted during the cooldown, so the server asks to wait before it is accepted.
+        let start = Instant::now();
+        assert_eq!(submit("42"), ValidationResult::Accepted);
+        assert!(start.elapsed() >= Duration::from_millis(500));
+        assert_eq!(next_unsolved_day(&log_path, 2025), Some(1));
+        assert_eq!(
+            check_submission_log(&log_path, 2025, 3, 1, "42"),
+            Some(ValidationResult::Accepted)
+        );
+    }
+
+    #[test]
+    fn reconciles_calendar_stars() {
+        let log_dir = tempfile::tempdir().expect("creating log directory");
+        let log_path = log_dir.path().join("results.toml");
+        record_submission_log(&log_path, 2025, 1, 1, "7", &ValidationResult::Accepted)
+            .expect("recording submission");
+
+        let stars = BTreeMap::from([(1, 2), (2, 2), (3, 1), (4, 0)]);
+        assert_eq!(
+            reconcile_stars(&log_path, 2025, &stars).expect("updating log"),
+            4
+        );
+        assert_eq!(
+            reconcile_stars(&log_path, 2025, &stars).expect("updating log"),
+            0
+        );
+        assert_eq!(next_unsolved_day(&log_path, 2025), Some(3));
+        assert!(is_solved(&log_path, 2025, 3, 1));
+        assert!(!is_solved(&log_path, 2025, 3, 2));
+        assert_eq!(
+            check_submission_log(&log_path, 2025, 1, 1, "7"),
+            Some(ValidationResult::Accepted)
+        );
+        assert_eq!(
+            check_submission_log(&log_path, 2025, 2, 1, "7"),
+            Some(ValidationResult::WrongLevel)
+        );
+    }
+
+    #[test]
+    fn reports_every_day_solved() {
+        let log_dir = tempfile::tempdir().expect("creating log directory");
+        let log_path = log_dir.path().join("results.toml");
+        let stars = (1..=12).map(|day| (day, 2)).collect();
+        reconcile_stars(&log_path, 2025, &stars).expect("updating log");
+        assert_eq!(next_unsolved_day(&log_path, 2025), None);
+    }
+
+    #[test]
+    fn imports_accepted_answers() {
+        let log_dir = tempfile::tempdir().expect("creating log directory");
+        let log_path = log_dir.path().join("results.toml");
+        record_submission_log(
+            &log_path,
+            2025,
+            5,
+            1,
+            "10",
+            &ValidationResult::RejectedTooLow,
+        )
+        .expect("recording submission");
+        let answers = [String::from("12"), String::from("abc")];
+        assert_eq!(
+            import_accepted_answers(&log_path, 2025, 5, &answers).expect("updating log"),
+            2
+        );
+        assert_eq!(
+            import_accepted_answers(&log_path, 2025, 5, &answers).expect("updating log"),
+            0
+        );
+        assert_eq!(
+            check_submission_log(&log_path, 2025, 5, 1, "12"),
+            Some(ValidationResult::Accepted)
+        );
+        assert_eq!(
+            check_submission_log(&log_path, 2025, 5, 2, "abd"),
+            Some(ValidationResult::Rejected)
+        );
+    }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let log_dir = tempfile::tempdir().expect("creating log directory");
+        let log_path = log_dir.path().join("results.toml");
+        thread::scope(|scope| {
+            for level in 1..=2 {
+                let log_path = &log_path;
+                scope.spawn(move || {
+                    for day in 1..=20 {
+                        record_submission_log(
+                            log_path,
+                            2024,
+                            day,
+                            level,
+                            "1",
+                            &ValidationResult::Accepted,
+                        )
+                        .expect("recording submission");
+                    }
+                });
+            }
+        });
+        assert_eq!(next_unsolved_day(&log_path, 2024), Some(21));
+        let backup = fs::read_to_string(sibling_path(&log_path, ".bak")).expect("reading backup");
+        let backup = parse_submission_log(&backup).expect("parsing backup");
+        assert_eq!(backup.answers.len(), 39);
+        assert!(!sibling_path(&log_path, ".tmp").exists());
+    }
+
+    #[test]
+    fn unparseable_log_is_not_overwritten() {
+        let log_dir = tempfile::tempdir().expect("creating log directory");
+        let log_path = log_dir.path().join("results.toml");
+        fs::write(&log_path, "answers = 7").expect("writing log");
+        let result = record_submission_log(&log_path, 2025, 1, 1, "7", &ValidationResult::Accepted);
+        assert_eq!(
+            result.expect_err("updating corrupt log").kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(
+            fs::read_to_string(&log_path).expect("reading log"),
+            "answers = 7"
+        );
+
+        // The submission went through, so its result is returned anyway.
+        fs::write(
+            log_dir.path().join("answers.toml"),
+            "\"2025/1/1\" = \"7\"\n",
+        )
+        .expect("writing answer key");
+        let source = DirectorySource::new(log_dir.path()).expect("opening source");
+        assert_eq!(
+            submit_with_cache(&log_path, &source, 2025, 1, 1, "7").expect("submitting answer"),
+            ValidationResult::Accepted
+        );
+    }
 }
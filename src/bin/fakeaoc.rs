@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+
+use aoc25::fakeserver::FakeAoc;
+
+/// Serves puzzle inputs and checks answers like adventofcode.com, for testing the solver
+/// against a local server with `aoc25 --base-url`.
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[arg(short, long, default_value_t = 8025)]
+    port: u16,
+
+    /// Directory with the puzzle inputs, laid out as `<year>/dayNN.txt`.
+    #[arg(short, long, default_value = "inputs")]
+    inputs: PathBuf,
+
+    /// TOML file with the correct answers, as `"<year>/<day>/<part>" = "<answer>"`.
+    #[arg(short, long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Seconds during which submissions are refused after a wrong answer.
+    #[arg(short, long, default_value_t = 60)]
+    cooldown: u64,
+}
+
+fn main() {
+    pretty_env_logger::init();
+    let args = Args::parse();
+    let answers = match FakeAoc::read_answers(&args.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            log::error!("error reading {}: {e}", args.answers.display());
+            return;
+        }
+    };
+    let aoc = FakeAoc::new(args.inputs, answers, Duration::from_secs(args.cooldown));
+    let server = match tiny_http::Server::http(("127.0.0.1", args.port)) {
+        Ok(server) => server,
+        Err(e) => {
+            log::error!("error listening on port {}: {e}", args.port);
+            return;
+        }
+    };
+    log::info!(
+        "serving fake Advent of Code on http://127.0.0.1:{}/",
+        args.port
+    );
+    aoc.serve(&server);
+}
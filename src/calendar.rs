@@ -0,0 +1,123 @@
+use std::thread::sleep;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::FixedOffset;
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono::Utc;
+
+/// Extra time to wait after the unlock, so that the request does not arrive before the
+/// server considers the puzzle unlocked.
+const UNLOCK_MARGIN: Duration = Duration::from_secs(2);
+
+/// Advent of Code runs on US Eastern time, puzzles unlock at midnight UTC-5.
+fn aoc_timezone() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).expect("UTC-5 offset")
+}
+
+/// Returns the year of the latest Advent of Code event that has already started.
+pub fn current_event_year() -> i16 {
+    let now = Utc::now().with_timezone(&aoc_timezone());
+    let year = now.year() as i16;
+    if now.month() == 12 { year } else { year - 1 }
+}
+
+/// Returns the number of puzzles in the event, which was cut from 25 to 12 in 2025.
+pub fn days_in_event(year: i16) -> i8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// Returns the instant when the puzzle of the given day becomes available, or None if
+/// the event has no puzzle for the day.
+pub fn unlock_time(year: i16, day: i8) -> Option<DateTime<Utc>> {
+    if !(1..=days_in_event(year)).contains(&day) {
+        return None;
+    }
+    let midnight =
+        NaiveDate::from_ymd_opt(year.into(), 12, day.try_into().ok()?)?.and_hms_opt(0, 0, 0)?;
+    Some(
+        aoc_timezone()
+            .from_local_datetime(&midnight)
+            .single()?
+            .with_timezone(&Utc),
+    )
+}
+
+/// Returns how long until the puzzle unlocks, or None if it is already available.
+pub fn time_until_unlock(year: i16, day: i8) -> Option<Duration> {
+    (unlock_time(year, day)? - Utc::now()).to_std().ok()
+}
+
+/// Returns the days of the event whose puzzles are already available.
+pub fn unlocked_days(year: i16) -> Vec<i8> {
+    let now = Utc::now();
+    (1..=days_in_event(year))
+        .filter(|&day| unlock_time(year, day).is_some_and(|unlock| unlock <= now))
+        .collect()
+}
+
+pub fn format_countdown(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Sleeps until the puzzle unlocks, logging a countdown along the way. Returns
+/// immediately if the puzzle is already available.
+pub fn wait_for_unlock(year: i16, day: i8) {
+    if time_until_unlock(year, day).is_none() {
+        return;
+    }
+    while let Some(remaining) = time_until_unlock(year, day) {
+        log::info!(
+            "{year} day {day} unlocks in {}",
+            format_countdown(remaining)
+        );
+        let step = if remaining > Duration::from_secs(60) {
+            Duration::from_secs(60)
+        } else if remaining > Duration::from_secs(10) {
+            Duration::from_secs(10)
+        } else {
+            remaining
+        };
+        sleep(step);
+    }
+    sleep(UNLOCK_MARGIN);
+    log::info!("{year} day {day} is unlocked");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlocks_at_midnight_eastern() {
+        assert_eq!(
+            unlock_time(2025, 1),
+            Utc.with_ymd_and_hms(2025, 12, 1, 5, 0, 0).single()
+        );
+        assert_eq!(
+            unlock_time(2018, 25),
+            Utc.with_ymd_and_hms(2018, 12, 25, 5, 0, 0).single()
+        );
+        assert_eq!(unlock_time(2018, 32), None);
+        assert_eq!(unlock_time(2018, 0), None);
+        // The event was shortened to 12 days.
+        assert!(unlock_time(2025, 12).is_some());
+        assert_eq!(unlock_time(2025, 13), None);
+    }
+
+    #[test]
+    fn past_puzzles_are_unlocked() {
+        assert_eq!(time_until_unlock(2015, 1), None);
+        assert!(time_until_unlock(2999, 1).is_some());
+        assert_eq!(unlocked_days(2015), (1..=25).collect::<Vec<_>>());
+        assert!(unlocked_days(2999).is_empty());
+    }
+}
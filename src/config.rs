@@ -0,0 +1,11 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Returns the directory with the configuration and state shared by all runs of the
+/// tool, `$XDG_CONFIG_HOME/aoc` or `~/.config/aoc`.
+pub fn dir() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(env::var_os("HOME")?).join(".config")))?;
+    Some(config_home.join("aoc"))
+}
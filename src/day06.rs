@@ -105,17 +105,14 @@ pub fn solver() -> Day6Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures;
     use test_log::test;
 
     #[test]
     fn provided_example() {
-        let example = "123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   +  ";
         let mut s = solver();
-        s.presolve(example);
-        assert_eq!("4277556", s.solve_part_one());
-        assert_eq!("3263827", s.solve_part_two());
+        s.presolve(&fixtures::example(2025, 6, 1));
+        assert_eq!(fixtures::answer(2025, 6, 1), s.solve_part_one());
+        assert_eq!(fixtures::answer(2025, 6, 2), s.solve_part_two());
     }
 }
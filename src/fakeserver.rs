@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use regex::Regex;
+use reqwest::Url;
+
+/// Impersonates the Advent of Code endpoints used by AocClient: serves puzzle inputs from
+/// a directory laid out like the input cache (`<year>/dayNN.txt`) and checks submitted
+/// answers against an answer key, replying with the same messages as the real site.
+pub struct FakeAoc {
+    inputs: PathBuf,
+    /// Correct answers keyed by `<year>/<day>/<level>`.
+    answers: HashMap<String, String>,
+    /// How long after a wrong answer further submissions are refused.
+    cooldown: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Keys of the puzzle parts that have been solved.
+    solved: HashSet<String>,
+    cooldown_until: Option<Instant>,
+}
+
+/// An HTTP status and body to send back.
+pub type Reply = (u16, String);
+
+fn page(message: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>\n"
+    )
+}
+
+impl FakeAoc {
+    pub fn new(inputs: PathBuf, answers: HashMap<String, String>, cooldown: Duration) -> FakeAoc {
+        FakeAoc {
+            inputs,
+            answers,
+            cooldown,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Reads an answer key in TOML, with entries like `"2025/1/1" = "1078"`.
+    pub fn read_answers(path: &Path) -> io::Result<HashMap<String, String>> {
+        toml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Handles one request. `session` tells whether the request carried a session cookie.
+    pub fn handle(&self, method: &str, url: &str, session: bool, body: &str) -> Reply {
+        static ROUTE_RE: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^/(\d+)/day/(\d+)/(input|answer)$").expect("route regex")
+        });
+        let Some(caps) = ROUTE_RE.captures(url) else {
+            return (404, String::from("404 Not Found"));
+        };
+        let (year, day) = (&caps[1], caps[2].parse::<i8>().unwrap_or_default());
+        if !session {
+            return (
+                400,
+                String::from(
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+                ),
+            );
+        }
+        match (method, &caps[3]) {
+            ("GET", "input") => self.input(year, day),
+            ("POST", "answer") => self.answer(year, day, body),
+            _ => (405, String::from("405 Method Not Allowed")),
+        }
+    }
+
+    fn input(&self, year: &str, day: i8) -> Reply {
+        match fs::read_to_string(self.inputs.join(year).join(format!("day{day:02}.txt"))) {
+            Ok(input) => (200, input),
+            Err(_) => (404, String::from("404 Not Found")),
+        }
+    }
+
+    fn answer(&self, year: &str, day: i8, body: &str) -> Reply {
+        let form: HashMap<String, String> = Url::parse(&format!("http://localhost/?{body}"))
+            .map(|url| url.query_pairs().into_owned().collect())
+            .unwrap_or_default();
+        let (Some(level), Some(answer)) = (form.get("level"), form.get("answer")) else {
+            return (400, String::from("400 Bad Request"));
+        };
+        let key = format!("{year}/{day}/{level}");
+        let Some(expected) = self.answers.get(&key) else {
+            return (404, String::from("404 Not Found"));
+        };
+        let mut state = self.state.lock().unwrap();
+        let previous_solved = level == "1" || state.solved.contains(&format!("{year}/{day}/1"));
+        if state.solved.contains(&key) || !previous_solved {
+            return (
+                200,
+                page("You don't seem to be solving the right level.  Did you already complete it?"),
+            );
+        }
+        let now = Instant::now();
+        if let Some(until) = state.cooldown_until
+            && until > now
+        {
+            let left = (until - now).as_secs_f64().ceil() as u64;
+            let left = if left >= 60 {
+                format!("{}m {}s", left / 60, left % 60)
+            } else {
+                format!("{left}s")
+            };
+            return (
+                200,
+                page(&format!(
+                    "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {left} left to wait."
+                )),
+            );
+        }
+        if answer.trim() == expected {
+            state.solved.insert(key);
+            return (
+                200,
+                page(
+                    "That's the right answer!  You are one gold star closer to decorating the North Pole.",
+                ),
+            );
+        }
+        state.cooldown_until = Some(now + self.cooldown);
+        let hint = match (answer.trim().parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => "; your answer is too high",
+            (Ok(answer), Ok(expected)) if answer < expected => "; your answer is too low",
+            _ => "",
+        };
+        let wait = match self.cooldown.as_secs() / 60 {
+            0 => String::new(),
+            1 => String::from("  Please wait one minute before trying again."),
+            minutes => format!("  Please wait {minutes} minutes before trying again."),
+        };
+        (
+            200,
+            page(&format!(
+                "That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.{wait}"
+            )),
+        )
+    }
+
+    /// Answers requests arriving at the server until it is shut down.
+    pub fn serve(&self, server: &tiny_http::Server) {
+        for mut request in server.incoming_requests() {
+            let session = request.headers().iter().any(|header| {
+                header.field.equiv("Cookie") && header.value.as_str().contains("session=")
+            });
+            let mut body = String::new();
+            if let Err(e) = request.as_reader().read_to_string(&mut body) {
+                log::warn!("failed to read request body: {e}");
+            }
+            let (status, reply) =
+                self.handle(&request.method().to_string(), request.url(), session, &body);
+            log::info!("{} {} -> {status}", request.method(), request.url());
+            let response = tiny_http::Response::from_string(reply).with_status_code(status);
+            if let Err(e) = request.respond(response) {
+                log::warn!("failed to send response: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_aoc(cooldown: Duration) -> FakeAoc {
+        FakeAoc::new(
+            PathBuf::from("/nonexistent"),
+            HashMap::from([
+                (String::from("2025/1/1"), String::from("42")),
+                (String::from("2025/1/2"), String::from("abc")),
+            ]),
+            cooldown,
+        )
+    }
+
+    #[test]
+    fn reads_answer_key() {
+        let dir = tempfile::tempdir().expect("creating answers directory");
+        let path = dir.path().join("answers.toml");
+        fs::write(&path, "\"2025/1/1\" = \"1078\"\n").expect("writing answers");
+        assert_eq!(
+            FakeAoc::read_answers(&path).expect("reading answers"),
+            HashMap::from([(String::from("2025/1/1"), String::from("1078"))])
+        );
+    }
+
+    #[test]
+    fn requires_session() {
+        assert_eq!(
+            fake_aoc(Duration::ZERO)
+                .handle("GET", "/2025/day/1/input", false, "")
+                .0,
+            400
+        );
+    }
+
+    #[test]
+    fn checks_answers_in_order() {
+        let aoc = fake_aoc(Duration::ZERO);
+        let submit = |level: i8, answer: &str| {
+            aoc.handle(
+                "POST",
+                "/2025/day/1/answer",
+                true,
+                &format!("level={level}&answer={answer}"),
+            )
+            .1
+        };
+        assert!(submit(2, "abc").contains("You don't seem to be solving the right level"));
+        assert!(submit(1, "43").contains("your answer is too high"));
+        assert!(submit(1, "41").contains("your answer is too low"));
+        assert!(submit(1, "42").contains("That's the right answer"));
+        assert!(submit(1, "42").contains("You don't seem to be solving the right level"));
+        assert!(submit(2, "abd").contains("That's not the right answer."));
+        assert!(submit(2, "abc").contains("That's the right answer"));
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let aoc = fake_aoc(Duration::from_secs(90));
+        let submit = |answer: &str| {
+            aoc.handle(
+                "POST",
+                "/2025/day/1/answer",
+                true,
+                &format!("level=1&answer={answer}"),
+            )
+            .1
+        };
+        assert!(submit("40").contains("Please wait one minute before trying again."));
+        assert!(submit("42").contains("You have 1m 30s left to wait."));
+    }
+}
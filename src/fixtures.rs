@@ -0,0 +1,106 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::aocclient::PuzzleExamples;
+
+/// Directory with example inputs and answers extracted from the puzzle pages, laid out
+/// as `fixtures/<year>/dayNN/exampleN.txt` and `fixtures/<year>/dayNN/answerN.txt`.
+pub const DIR: &str = "fixtures";
+
+fn day_dir(root: &Path, year: i16, day: i8) -> PathBuf {
+    root.join(year.to_string()).join(format!("day{day:02}"))
+}
+
+/// Writes the examples as fixture files and returns the paths that were written.
+/// Existing files are kept unless `overwrite` is set, so that fixtures can be fixed
+/// up by hand.
+pub fn write(
+    root: &Path,
+    year: i16,
+    day: i8,
+    examples: &PuzzleExamples,
+    overwrite: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let dir = day_dir(root, year, day);
+    fs::create_dir_all(&dir)?;
+    let inputs = examples
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| (dir.join(format!("example{}.txt", i + 1)), input.clone()));
+    let answers = examples
+        .answers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, answer)| {
+            answer.as_ref().map(|answer| {
+                (
+                    dir.join(format!("answer{}.txt", i + 1)),
+                    answer.clone() + "\n",
+                )
+            })
+        });
+    let mut written = vec![];
+    for (path, contents) in inputs.chain(answers) {
+        if overwrite || !path.exists() {
+            fs::write(&path, contents)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+fn read_fixture(year: i16, day: i8, name: &str) -> String {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join(DIR);
+    let path = day_dir(&root, year, day).join(name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("reading {}: {e}", path.display()))
+}
+
+/// Loads the n-th example input of the day.
+#[cfg(test)]
+pub fn example(year: i16, day: i8, n: usize) -> String {
+    read_fixture(year, day, &format!("example{n}.txt"))
+}
+
+/// Loads the expected answer for the examples of the given part of the day.
+#[cfg(test)]
+pub fn answer(year: i16, day: i8, part: i8) -> String {
+    read_fixture(year, day, &format!("answer{part}.txt"))
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_fixtures_without_overwriting() {
+        let root = tempfile::tempdir().expect("creating fixtures directory");
+        let examples = PuzzleExamples {
+            inputs: vec![String::from("1 2\n3 4\n"), String::from("5\n")],
+            answers: vec![None, Some(String::from("42"))],
+        };
+        let dir = root.path().join("2025").join("day03");
+        fs::create_dir_all(&dir).expect("creating day directory");
+        fs::write(dir.join("example2.txt"), "fixed up by hand\n").expect("writing example");
+
+        let written = write(root.path(), 2025, 3, &examples, false).expect("writing fixtures");
+        assert_eq!(
+            written,
+            vec![dir.join("example1.txt"), dir.join("answer2.txt")]
+        );
+        let read = |name| fs::read_to_string(dir.join(name)).expect("reading fixture");
+        assert_eq!(read("example1.txt"), "1 2\n3 4\n");
+        assert_eq!(read("example2.txt"), "fixed up by hand\n");
+        assert_eq!(read("answer2.txt"), "42\n");
+        assert!(!dir.join("answer1.txt").exists());
+
+        let written = write(root.path(), 2025, 3, &examples, true).expect("writing fixtures");
+        assert_eq!(written.len(), 3);
+        assert_eq!(read("example2.txt"), "5\n");
+    }
+}
@@ -0,0 +1,102 @@
+use std::cmp::Reverse;
+
+use itertools::Itertools;
+
+use crate::aocclient::Leaderboard;
+use crate::aocclient::Member;
+
+fn star_mark(member: &Member, day: i8) -> char {
+    match (member.star(day, 1), member.star(day, 2)) {
+        (Some(_), Some(_)) => '*',
+        (Some(_), None) => '+',
+        _ => '.',
+    }
+}
+
+fn format_elapsed(seconds: i64) -> String {
+    if seconds >= 24 * 3600 {
+        String::from(">24h")
+    } else {
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+/// Returns the latest day for which any member of the leaderboard has a star.
+pub fn latest_day(leaderboard: &Leaderboard) -> Option<i8> {
+    leaderboard
+        .members
+        .values()
+        .filter_map(|member| member.completion_day_level.keys().max())
+        .max()
+        .copied()
+}
+
+/// Renders the leaderboard as a table ranked by local score. Every row has a mark for
+/// each of the event's days (`*` for both stars, `+` for one) and the time it took the
+/// member to get the stars of `day`, counted from its unlock.
+pub fn render(leaderboard: &Leaderboard, days: i8, day: i8) -> String {
+    const PREFIX: &str = "           ";
+    let mut table = String::new();
+    if days >= 10 {
+        table.push_str(PREFIX);
+        for d in 1..=days {
+            table.push(if d >= 10 {
+                char::from(b'0' + (d / 10) as u8)
+            } else {
+                ' '
+            });
+        }
+        table.push('\n');
+    }
+    table.push_str(PREFIX);
+    for d in 1..=days {
+        table.push(char::from(b'0' + (d % 10) as u8));
+    }
+    table.push_str(&format!("  {:>8}  {:>8}\n", "part 1", "part 2"));
+    let members = leaderboard
+        .members
+        .values()
+        .sorted_by_key(|member| (Reverse(member.local_score), member.last_star_ts, member.id));
+    for (rank, member) in members.enumerate() {
+        let marks: String = (1..=days).map(|d| star_mark(member, d)).collect();
+        let [part_one, part_two] = [1, 2].map(|part| {
+            member.star(day, part).map_or(String::new(), |star| {
+                format_elapsed(star.get_star_ts - leaderboard.unlock_ts(day))
+            })
+        });
+        table.push_str(&format!(
+            "{:>3}) {:>5} {marks}  {part_one:>8}  {part_two:>8}  {}\n",
+            rank + 1,
+            member.local_score,
+            member.display_name()
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_ranked_table() {
+        let leaderboard: Leaderboard =
+            serde_json::from_str(include_str!("aocclient/testdata/leaderboard.json"))
+                .expect("parsing leaderboard");
+        assert_eq!(latest_day(&leaderboard), Some(2));
+        assert_eq!(
+            render(&leaderboard, 12, 1),
+            "                    111
+           123456789012    part 1    part 2
+  1)    10 *+..........  00:05:00  00:15:00  Alice
+  2)     2 +...........  01:20:00            (anonymous user #1002)
+  3)     0 ............                      Carol
+"
+        );
+    }
+}
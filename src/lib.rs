@@ -0,0 +1,8 @@
+//! Advent of Code client shared by the solver binary and other programs, and a fake
+//! server to test it against. Build with the `async` feature for the Tokio-based client
+//! in `aocclient::nonblocking`.
+
+pub mod aocclient;
+pub mod calendar;
+pub mod config;
+pub mod fakeserver;
@@ -1,4 +1,3 @@
-mod aocclient;
 mod autosubmit;
 mod day01;
 mod day02;
@@ -12,48 +11,205 @@ mod day09;
 mod day10;
 mod day11;
 mod day12;
+mod fixtures;
+mod leaderboard;
+mod profile;
+mod session;
 mod solver;
+mod source;
+mod sync;
 
 use std::{
-    env,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
+use aoc25::aocclient;
+use aoc25::calendar;
+use aoc25::config;
 use autosubmit::next_unsolved_day;
+use clap::CommandFactory;
 use clap::Parser;
+use clap::Subcommand;
+use profile::Profile;
 
 use solver::Solver;
+use source::DirectorySource;
+use source::PuzzleSource;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(short, long)]
     submit: bool,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     cookie: Option<String>,
 
+    /// Account to use, with its own session cookie, input cache and submission log.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[arg(short, long)]
     part_two_only: bool,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     day: Option<i8>,
+
+    /// Event year, defaults to the latest event that has started.
+    #[arg(short, long, global = true)]
+    year: Option<i16>,
+
+    /// Download puzzle inputs and descriptions even if they are already cached.
+    #[arg(short, long, global = true)]
+    refresh: bool,
+
+    /// Minimum number of seconds between requests to adventofcode.com.
+    #[arg(long, global = true, value_parser = parse_seconds)]
+    request_interval: Option<Duration>,
+
+    /// Minimum number of seconds between answer submissions.
+    #[arg(long, global = true, value_parser = parse_seconds)]
+    submission_interval: Option<Duration>,
+
+    /// Save every request to adventofcode.com and its response in the directory.
+    #[arg(long, global = true, conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Serve responses from a directory written by --record instead of the network.
+    #[arg(long, global = true)]
+    replay: Option<PathBuf>,
+
+    /// Log every request to adventofcode.com with its status, timing and the start of
+    /// the response, with the session cookie redacted.
+    #[arg(long, global = true)]
+    trace_http: bool,
+
+    /// Never contact adventofcode.com: inputs must be in the cache, and answers are only
+    /// checked against the submission log.
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Site to send requests to instead of adventofcode.com, such as a local `fakeaoc`.
+    #[arg(long, global = true, default_value = aocclient::BASE_URL)]
+    base_url: String,
+
+    /// Seconds to wait for a request to adventofcode.com to complete.
+    #[arg(long, global = true, value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Seconds to wait for the connection to adventofcode.com.
+    #[arg(long, global = true, value_parser = parse_timeout)]
+    connect_timeout: Option<Duration>,
+
+    /// HTTP(S) proxy for requests to adventofcode.com.
+    #[arg(long, global = true)]
+    proxy: Option<String>,
+
+    /// PEM file with additional certificates to trust, e.g. for an intercepting proxy.
+    #[arg(long, global = true)]
+    ca_bundle: Option<PathBuf>,
+
+    /// Email address or other contact included in the user agent, as the AoC
+    /// automation guidelines ask. Defaults to the contents of the `contact` file next to
+    /// the session cookie file of the profile.
+    #[arg(long, global = true)]
+    contact: Option<String>,
+
+    /// Solve with the inputs in the directory (`<year>/dayNN.txt`) and check answers
+    /// against its `answers.toml` instead of adventofcode.com.
+    #[arg(long)]
+    source_dir: Option<PathBuf>,
 }
 
-fn solver_for_day(day: i8) -> Option<Box<dyn Solver>> {
-    match day {
-        1 => Some(Box::new(day01::solver())),
-        2 => Some(Box::new(day02::solver())),
-        3 => Some(Box::new(day03::solver())),
-        4 => Some(Box::new(day04::solver())),
-        5 => Some(Box::new(day05::solver())),
-        6 => Some(Box::new(day06::solver())),
-        7 => Some(Box::new(day07::solver())),
-        8 => Some(Box::new(day08::solver())),
-        9 => Some(Box::new(day09::solver())),
-        10 => Some(Box::new(day10::solver())),
-        11 => Some(Box::new(day11::solver())),
-        12 => Some(Box::new(day12::solver())),
+#[derive(Subcommand)]
+enum Command {
+    /// Print the puzzle description as Markdown.
+    Describe,
+    /// Save the examples from the puzzle description as test fixtures.
+    Examples {
+        /// Replace fixture files that already exist.
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Print a private leaderboard, with solve times for the chosen day.
+    Leaderboard {
+        /// Leaderboard ID, as seen in its URL.
+        id: u64,
+    },
+    /// Check the session cookie and print the name of the logged-in user.
+    Whoami,
+    /// Download the inputs and descriptions of every unlocked day of the year that are
+    /// not cached yet, and the descriptions that do not have part two yet.
+    Sync,
+    /// Copy the accepted answers shown on the day pages into the submission log, for the
+    /// chosen day or every day with stars.
+    Import,
+    /// List the archived responses to answer submissions, or show one of them.
+    Submissions {
+        /// Number of the response to show, as printed in the list.
+        index: Option<usize>,
+    },
+}
+
+/// Parses a command line argument given in seconds, which may be fractional.
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let seconds = arg.parse::<f64>().map_err(|e| e.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+}
+
+/// Parses a timeout in seconds, which unlike an interval cannot be zero.
+fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    let timeout = parse_seconds(arg)?;
+    if timeout.is_zero() {
+        return Err(String::from("a timeout must be longer than zero"));
+    }
+    Ok(timeout)
+}
+
+/// Longest time to wait for a puzzle to unlock before giving up.
+const MAX_UNLOCK_WAIT: Duration = Duration::from_secs(3600);
+
+fn solver_for(year: i16, day: i8) -> Option<Box<dyn Solver>> {
+    match (year, day) {
+        (2025, 1) => Some(Box::new(day01::solver())),
+        (2025, 2) => Some(Box::new(day02::solver())),
+        (2025, 3) => Some(Box::new(day03::solver())),
+        (2025, 4) => Some(Box::new(day04::solver())),
+        (2025, 5) => Some(Box::new(day05::solver())),
+        (2025, 6) => Some(Box::new(day06::solver())),
+        (2025, 7) => Some(Box::new(day07::solver())),
+        (2025, 8) => Some(Box::new(day08::solver())),
+        (2025, 9) => Some(Box::new(day09::solver())),
+        (2025, 10) => Some(Box::new(day10::solver())),
+        (2025, 11) => Some(Box::new(day11::solver())),
+        (2025, 12) => Some(Box::new(day12::solver())),
+        _ => None,
+    }
+}
+
+/// Suggests what the user can do about an error returned by the AoC client.
+fn error_guidance(e: &aocclient::Error) -> Option<&'static str> {
+    match e {
+        aocclient::Error::SessionExpired => Some(
+            "log in to adventofcode.com and refresh your session cookie (--cookie, AOC_COOKIE or ~/.config/aoc/session)",
+        ),
+        aocclient::Error::NotUnlocked(_) => Some("check the year, day and leaderboard ID"),
+        aocclient::Error::ServerFailure(_) => Some("the site may be overloaded, try again shortly"),
+        aocclient::Error::UnparseableResponse(_) => {
+            Some("check the puzzle page in a browser to see what the site said")
+        }
+        aocclient::Error::ReplayMiss(_) => Some("record the request first with --record"),
+        aocclient::Error::Offline(_) => {
+            Some("run once without --offline to download the input or submit the answer")
+        }
+        aocclient::Error::Locked(_) => Some("run again closer to the unlock, or pick another day"),
+        aocclient::Error::AnswerKey(_) => {
+            Some("add the answer as \"<year>/<day>/<part>\" = \"<answer>\" to answers.toml")
+        }
         _ => None,
     }
 }
@@ -64,66 +220,420 @@ fn timeit<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
     (result, start.elapsed())
 }
 
-fn main() {
-    pretty_env_logger::init();
-    log::info!("Advent of Code 2025 Solver");
-
-    let args = Args::parse();
+fn report_error(context: &str, e: &aocclient::Error) {
+    log::error!("{context}: {e}");
+    if let Some(guidance) = error_guidance(e) {
+        log::error!("{guidance}");
+    }
+}
 
-    let client = aocclient::AocClient::new().expect("creating AoC client");
+/// Waits for the puzzle to unlock if it will do so soon. Returns false if the unlock is
+/// too far away to wait for.
+fn wait_for_puzzle(year: i16, day: i8) -> bool {
+    match calendar::time_until_unlock(year, day) {
+        Some(remaining) if remaining > MAX_UNLOCK_WAIT => {
+            log::error!(
+                "{year} day {day} unlocks in {}, not waiting that long",
+                calendar::format_countdown(remaining)
+            );
+            false
+        }
+        _ => {
+            calendar::wait_for_unlock(year, day);
+            true
+        }
+    }
+}
 
-    // Get the AoC cookie, either from the command line, or from the env variable.
-    if let Some(cookie) = args.cookie {
-        client.set_cookie(cookie.as_str());
-    } else if let Ok(cookie) = env::var("AOC_COOKIE") {
-        client.set_cookie(&cookie);
-    } else {
-        log::warn!("you must specify the session cookie with --cookie or AOC_COOKIE env variable");
+fn describe(client: &aocclient::AocClient, year: i16, day: i8, refresh: bool) {
+    if !wait_for_puzzle(year, day) {
         return;
     }
-    let day = if let Some(day) = args.day {
-        day
+    let description = if refresh {
+        client.fetch_puzzle_description(year, day)
     } else {
-        next_unsolved_day()
+        client.get_puzzle_description(year, day)
     };
-    let solver = solver_for_day(day);
-    if solver.is_none() {
-        log::error!("this solver cannot solve day {}", day);
+    match description {
+        Ok(description) => println!("{description}"),
+        Err(e) => report_error("error retrieving puzzle description", &e),
+    }
+}
+
+fn extract_examples(client: &aocclient::AocClient, year: i16, day: i8, overwrite: bool) {
+    let examples = match client.fetch_puzzle_examples(year, day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            report_error("error retrieving puzzle examples", &e);
+            return;
+        }
+    };
+    match fixtures::write(Path::new(fixtures::DIR), year, day, &examples, overwrite) {
+        Ok(written) => {
+            for path in &written {
+                log::info!("wrote {}", path.display());
+            }
+            log::info!(
+                "found {} examples and {} answers, wrote {} files",
+                examples.inputs.len(),
+                examples.answers.iter().flatten().count(),
+                written.len()
+            );
+        }
+        Err(e) => log::error!("error writing fixtures: {e}"),
+    }
+}
+
+fn print_leaderboard(client: &aocclient::AocClient, year: i16, day: Option<i8>, id: u64) {
+    match client.get_private_leaderboard(year, id) {
+        Ok(board) => {
+            let day = day.or_else(|| leaderboard::latest_day(&board)).unwrap_or(1);
+            print!(
+                "{}",
+                leaderboard::render(&board, calendar::days_in_event(year), day)
+            );
+        }
+        Err(e) => report_error("error retrieving leaderboard", &e),
+    }
+}
+
+/// Checks that the session cookie is still accepted, logging the user it belongs to.
+fn check_session(client: &aocclient::AocClient, year: i16) -> bool {
+    match client.whoami(year) {
+        Ok(name) => {
+            log::info!("logged in as {name}");
+            true
+        }
+        Err(e) => {
+            report_error("error checking the session", &e);
+            false
+        }
+    }
+}
+
+fn show_submissions(profile: &Profile, index: Option<usize>) {
+    let responses = match aocclient::SubmissionArchive::new(profile.archive_dir()).list() {
+        Ok(responses) => responses,
+        Err(e) => {
+            log::error!("error reading the submission archive: {e}");
+            return;
+        }
+    };
+    let Some(index) = index else {
+        for (i, response) in responses.iter().enumerate() {
+            println!(
+                "{:>4}  {}  {} day {:>2} part {}  {}",
+                i + 1,
+                response.submitted_at,
+                response.year,
+                response.day,
+                response.level,
+                response.answer
+            );
+        }
         return;
+    };
+    match index.checked_sub(1).and_then(|i| responses.get(i)) {
+        Some(response) => print!("{}", response.body),
+        None => log::error!("there is no archived response number {index}"),
+    }
+}
+
+fn import_answers(client: &aocclient::AocClient, log_path: &Path, year: i16, day: Option<i8>) {
+    let days: Vec<i8> = match day {
+        Some(day) => vec![day],
+        None => match client.fetch_stars(year) {
+            Ok(stars) => stars
+                .into_iter()
+                .filter(|&(_, count)| count > 0)
+                .map(|(day, _)| day)
+                .collect(),
+            Err(e) => {
+                report_error("error retrieving the event calendar", &e);
+                return;
+            }
+        },
+    };
+    let mut imported = 0;
+    for day in days {
+        match client.fetch_accepted_answers(year, day) {
+            Ok(answers) => {
+                match autosubmit::import_accepted_answers(log_path, year, day, &answers) {
+                    Ok(count) => imported += count,
+                    Err(e) => log::error!("error updating {}: {e}", log_path.display()),
+                }
+            }
+            Err(e) => report_error(&format!("error retrieving {year} day {day}"), &e),
+        }
+    }
+    log::info!(
+        "imported {imported} accepted answers into {}",
+        log_path.display()
+    );
+}
+
+/// Records the stars shown on the event calendar in the submission log, so that puzzles
+/// solved elsewhere are not picked again.
+fn sync_stars(client: &aocclient::AocClient, log_path: &Path, year: i16) {
+    match client.fetch_stars(year) {
+        Ok(stars) => match autosubmit::reconcile_stars(log_path, year, &stars) {
+            Ok(0) => {}
+            Ok(updated) => log::info!("marked {updated} parts solved on the site as completed"),
+            Err(e) => log::error!("error updating {}: {e}", log_path.display()),
+        },
+        Err(e) => report_error("error retrieving the event calendar", &e),
     }
-    let mut solver = solver.unwrap();
+}
+
+fn submit(source: &dyn PuzzleSource, log_path: &Path, year: i16, day: i8, level: i8, answer: &str) {
+    log::info!("submitting part {level}...");
+    match autosubmit::submit_with_cache(log_path, source, year, day, level, answer) {
+        Ok(result) => log::info!("part {level} submission result: {result:?}"),
+        Err(e) => report_error(&format!("part {level} was not submitted"), &e),
+    }
+}
+
+/// Returns the solver for the day, logging an error if there is none.
+fn find_solver(year: i16, day: i8) -> Option<Box<dyn Solver>> {
+    let solver = solver_for(year, day);
+    if solver.is_none() {
+        log::error!("this solver cannot solve {} day {}", year, day);
+    }
+    solver
+}
+
+fn solve(
+    mut solver: Box<dyn Solver>,
+    source: &dyn PuzzleSource,
+    args: &Args,
+    log_path: &Path,
+    year: i16,
+    day: i8,
+) {
     let solver = solver.as_mut();
-    log::info!("solving Advent of Code day {}", day);
+    log::info!("solving Advent of Code {} day {}", year, day);
     log::info!("retrieving puzzle input...");
-    match client.get_puzzle_input(day) {
+    let input = if args.refresh {
+        source.fetch_input(year, day)
+    } else {
+        source.get_input(year, day)
+    };
+    match input {
         Ok(input) => {
             solver.presolve(input.as_str());
-            if !args.part_two_only {
+            let skip_part_one = !args.part_two_only
+                && autosubmit::is_solved(log_path, year, day, 1)
+                && !autosubmit::is_solved(log_path, year, day, 2);
+            if skip_part_one {
+                log::info!("part one is already solved, skipping it");
+            }
+            if !args.part_two_only && !skip_part_one {
                 log::info!("solving part one...");
                 let (answer, part_one_time) = timeit(|| solver.solve_part_one());
                 log::info!("part one solved in {part_one_time:?}, answer: {answer}");
                 if args.submit {
-                    log::info!("submitting part one...");
-                    let result =
-                        autosubmit::submit_with_cache(day, 1, answer.as_str(), |d, l, a| {
-                            client.submit_answer(d, l, a).unwrap()
-                        });
-                    log::info!("part one submission result: {result:?}");
+                    submit(source, log_path, year, day, 1, &answer);
                 }
             }
             log::info!("solving part two...");
             let (answer, part_two_time) = timeit(|| solver.solve_part_two());
             log::info!("part two solved in {part_two_time:?}, answer: {answer}");
             if args.submit {
-                log::info!("submitting part two...");
-                let result = autosubmit::submit_with_cache(day, 2, answer.as_str(), |d, l, a| {
-                    client.submit_answer(d, l, a).unwrap()
-                });
-                log::info!("part one submission result: {result:?}");
+                submit(source, log_path, year, day, 2, &answer);
             }
         }
+        Err(e) => report_error("error retrieving puzzle input", &e),
+    }
+}
+
+/// Solves a puzzle from the directory given with --source-dir. Submissions are logged in
+/// the profile, apart from the log of the adventofcode.com answers.
+fn solve_from_directory(args: &Args, profile: &Profile, dir: &Path) {
+    let source = match DirectorySource::new(dir) {
+        Ok(source) => source,
         Err(e) => {
-            log::error!("error retrieving puzzle input: {e:#?}");
+            log::error!("error opening {}: {e}", dir.display());
+            return;
+        }
+    };
+    let log_path = profile.source_log(dir);
+    let year = args.year.unwrap_or_else(calendar::current_event_year);
+    let Some(day) = args.day.or_else(|| next_unsolved_day(&log_path, year)) else {
+        log::info!("all puzzles of {year} are solved, pick one with --day");
+        return;
+    };
+    if let Some(solver) = find_solver(year, day) {
+        solve(solver, &source, args, &log_path, year, day);
+    }
+}
+
+fn main() {
+    pretty_env_logger::init();
+    log::info!("Advent of Code Solver");
+
+    let args = Args::parse();
+    let profile = match &args.profile {
+        Some(name) => match Profile::named(name) {
+            Some(profile) => profile,
+            None => {
+                log::error!("invalid profile name {name:?}, use letters, digits, - and _");
+                return;
+            }
+        },
+        None => Profile::default(),
+    };
+    if let Some(dir) = &args.source_dir {
+        if args.command.is_some() {
+            Args::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    "--source-dir can only be used to solve puzzles, not with a subcommand",
+                )
+                .exit();
+        }
+        solve_from_directory(&args, &profile, dir);
+        return;
+    }
+    if let Some(Command::Submissions { index }) = args.command {
+        show_submissions(&profile, index);
+        return;
+    }
+
+    let mut throttle = aocclient::ThrottlePolicy::default();
+    if let Some(interval) = args.request_interval {
+        throttle.request_interval = interval;
+    }
+    if let Some(interval) = args.submission_interval {
+        throttle.submission_interval = interval;
+    }
+    let mut http = aocclient::HttpSettings {
+        proxy: args.proxy.clone(),
+        ca_bundle: args.ca_bundle.clone(),
+        contact: args.contact.clone().or_else(|| profile.contact()),
+        ..Default::default()
+    };
+    if let Some(timeout) = args.timeout {
+        http.timeout = timeout;
+    }
+    if let Some(timeout) = args.connect_timeout {
+        http.connect_timeout = timeout;
+    }
+    if http.contact.is_none() && !args.offline {
+        match profile.contact_file() {
+            Some(path) => log::warn!(
+                "pass --contact or put an email address in {} to include it in the user agent",
+                path.display()
+            ),
+            None => {
+                log::warn!("pass --contact with an email address to include it in the user agent")
+            }
+        }
+    }
+    let traffic = if let Some(dir) = &args.record {
+        aocclient::TrafficMode::Record(dir.clone())
+    } else if let Some(dir) = &args.replay {
+        aocclient::TrafficMode::Replay(dir.clone())
+    } else {
+        aocclient::TrafficMode::Live
+    };
+    let client = aocclient::AocClient::new_with_base(
+        &args.base_url,
+        aocclient::ClientOptions {
+            throttle: Some(throttle),
+            traffic,
+            trace_http: args.trace_http,
+            offline: args.offline,
+            http,
+            ..Default::default()
+        },
+    );
+    let mut client = match client {
+        Ok(client) => client,
+        Err(e) => {
+            report_error("error creating the HTTP client", &e);
+            return;
+        }
+    };
+    client.set_input_cache(profile.input_cache_dir());
+    client.set_submission_archive(aocclient::SubmissionArchive::new(profile.archive_dir()));
+
+    match session::find_cookie(args.cookie.as_deref(), &profile) {
+        Ok((cookie, source)) => {
+            log::info!("using the session cookie of the {profile} from {source}");
+            client.set_cookie(&cookie);
+        }
+        // No requests are sent, so the cookie is not needed.
+        Err(e) if args.offline => log::debug!("{e}"),
+        Err(e) => {
+            log::error!("{e}");
+            return;
+        }
+    }
+    let year = args.year.unwrap_or_else(calendar::current_event_year);
+    let log_path = profile.submission_log();
+    // Stars solved elsewhere matter only when the next unsolved day has to be picked.
+    let picks_day = args.day.is_none()
+        && matches!(
+            args.command,
+            None | Some(Command::Describe | Command::Examples { .. })
+        );
+    if picks_day && !args.offline {
+        sync_stars(&client, &log_path, year);
+    }
+    let day = match args.day.or_else(|| next_unsolved_day(&log_path, year)) {
+        Some(day) => day,
+        None if picks_day => {
+            log::info!("all puzzles of {year} are solved, pick one with --day");
+            return;
+        }
+        // The remaining commands work on the whole year or take the day from --day.
+        None => 0,
+    };
+    match args.command {
+        Some(Command::Describe) => describe(&client, year, day, args.refresh),
+        Some(Command::Examples { overwrite }) => extract_examples(&client, year, day, overwrite),
+        Some(Command::Leaderboard { id }) => print_leaderboard(&client, year, args.day, id),
+        // Handled before looking for the session cookie.
+        Some(Command::Submissions { .. }) => {}
+        Some(Command::Sync) => {
+            let days = calendar::unlocked_days(year);
+            let summary = sync::sync_cache(&client, year, &days, args.refresh);
+            println!("{year}: {} days unlocked, {summary}", days.len());
+        }
+        Some(Command::Import) => import_answers(&client, &log_path, year, args.day),
+        Some(Command::Whoami) => match client.whoami(year) {
+            Ok(name) => println!("{name}"),
+            Err(e) => report_error("error checking the session", &e),
+        },
+        None => {
+            let Some(solver) = find_solver(year, day) else {
+                return;
+            };
+            // Fail before a long solve rather than end up with an answer that cannot be
+            // submitted.
+            if args.submit && !args.offline && !check_session(&client, year) {
+                return;
+            }
+            if wait_for_puzzle(year, day) {
+                solve(solver, &client, &args, &log_path, year, day);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_seconds() {
+        assert_eq!(parse_seconds("2.5"), Ok(Duration::from_millis(2500)));
+        assert_eq!(parse_seconds("0"), Ok(Duration::ZERO));
+        for invalid in ["-1", "NaN", "inf", "1e30", "five"] {
+            assert!(parse_seconds(invalid).is_err(), "{invalid} was accepted");
         }
+        assert_eq!(parse_timeout("0.5"), Ok(Duration::from_millis(500)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("-1").is_err());
     }
 }
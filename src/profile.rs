@@ -0,0 +1,148 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::autosubmit;
+use crate::session;
+
+/// Directory holding the data of the named profiles, one subdirectory per profile.
+pub const DIR: &str = "profiles";
+
+/// An Advent of Code account. The default profile keeps its data in the current
+/// directory, as before profiles existed; named profiles keep it in `profiles/<name>`.
+#[derive(Debug, Default)]
+pub struct Profile {
+    name: Option<String>,
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "profile {name}"),
+            None => write!(f, "default profile"),
+        }
+    }
+}
+
+impl Profile {
+    /// Returns the named profile, or None if the name cannot be used in a path or an
+    /// environment variable.
+    pub fn named(name: &str) -> Option<Profile> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        valid.then(|| Profile {
+            name: Some(String::from(name)),
+        })
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        match &self.name {
+            Some(name) => PathBuf::from(DIR).join(name),
+            None => PathBuf::new(),
+        }
+    }
+
+    pub fn input_cache_dir(&self) -> PathBuf {
+        self.data_dir().join("inputs")
+    }
+
+    pub fn submission_log(&self) -> PathBuf {
+        self.data_dir().join(autosubmit::FILE)
+    }
+
+    /// Submission log for answers checked against a puzzle source directory, kept apart
+    /// from the adventofcode.com log and out of the directory, which may be shared or
+    /// read-only.
+    pub fn source_log(&self, source_dir: &Path) -> PathBuf {
+        let source_dir = source_dir
+            .canonicalize()
+            .unwrap_or_else(|_| source_dir.to_path_buf());
+        let name: String = source_dir
+            .to_string_lossy()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.data_dir().join("sources").join(name + ".toml")
+    }
+
+    pub fn archive_dir(&self) -> PathBuf {
+        self.data_dir().join("submissions")
+    }
+
+    /// Environment variable with the session cookie, `AOC_COOKIE_<NAME>` for named
+    /// profiles.
+    pub fn cookie_variable(&self) -> String {
+        match &self.name {
+            Some(name) => format!("AOC_COOKIE_{}", name.to_uppercase().replace('-', "_")),
+            None => String::from("AOC_COOKIE"),
+        }
+    }
+
+    /// File with the session cookie, `~/.config/aoc/profiles/<name>/session` for named
+    /// profiles.
+    pub fn cookie_file(&self) -> Option<PathBuf> {
+        let default = session::config_file()?;
+        match &self.name {
+            Some(name) => Some(default.parent()?.join(DIR).join(name).join("session")),
+            None => Some(default),
+        }
+    }
+
+    /// File with the contact for the user agent, next to the session cookie file.
+    pub fn contact_file(&self) -> Option<PathBuf> {
+        Some(self.cookie_file()?.with_file_name("contact"))
+    }
+
+    /// Returns the contact for the user agent from the contact file, if there is one.
+    pub fn contact(&self) -> Option<String> {
+        let contact = fs::read_to_string(self.contact_file()?).ok()?;
+        let contact = contact.trim();
+        (!contact.is_empty()).then(|| String::from(contact))
+    }
+
+    /// Whether the cookie may be taken from the browsers, which are logged in to a
+    /// single account that is assumed to be the default one.
+    pub fn uses_browser_cookies(&self) -> bool {
+        self.name.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_profiles_have_separate_paths() {
+        let default = Profile::default();
+        assert_eq!(default.submission_log(), PathBuf::from("results.toml"));
+        assert_eq!(default.cookie_variable(), "AOC_COOKIE");
+        let work = Profile::named("work-2").expect("valid profile name");
+        assert_eq!(
+            work.input_cache_dir(),
+            PathBuf::from("profiles/work-2/inputs")
+        );
+        assert_eq!(
+            work.submission_log(),
+            PathBuf::from("profiles/work-2/results.toml")
+        );
+        assert_eq!(work.cookie_variable(), "AOC_COOKIE_WORK_2");
+        assert!(!work.uses_browser_cookies());
+        if let Some(contact_file) = work.contact_file() {
+            assert!(contact_file.ends_with("aoc/profiles/work-2/contact"));
+        }
+        assert_eq!(
+            work.source_log(Path::new("/nonexistent/team inputs")),
+            PathBuf::from("profiles/work-2/sources/_nonexistent_team_inputs.toml")
+        );
+        assert!(Profile::named("../other").is_none());
+    }
+}
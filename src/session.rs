@@ -0,0 +1,426 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use aes::cipher::BlockDecryptMut;
+use aes::cipher::KeyIvInit;
+use aes::cipher::block_padding::Pkcs7;
+use regex::Regex;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use tempfile::TempDir;
+
+use crate::config;
+use crate::profile::Profile;
+
+/// Where the session cookie was found.
+#[derive(Debug, PartialEq)]
+pub enum CookieSource {
+    CommandLine,
+    /// The environment variable with the given name.
+    Environment(String),
+    File(PathBuf),
+    Firefox(PathBuf),
+    Chromium(PathBuf),
+}
+
+impl fmt::Display for CookieSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CommandLine => write!(f, "--cookie"),
+            Self::Environment(variable) => write!(f, "{variable} environment variable"),
+            Self::File(path) => write!(f, "file {}", path.display()),
+            Self::Firefox(path) => write!(f, "Firefox cookie database {}", path.display()),
+            Self::Chromium(path) => write!(f, "Chromium cookie database {}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// None of the sources had a session cookie. Names the environment variable and the
+    /// file that were checked.
+    NotFound(String, Option<PathBuf>),
+    /// The cookie from the source does not look like an AoC session cookie.
+    InvalidFormat(CookieSource),
+    /// The cookie file can be read by other users.
+    InsecurePermissions(PathBuf),
+    Io(PathBuf, io::Error),
+    /// A browser cookie database could not be queried.
+    Database(PathBuf, rusqlite::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound(variable, file) => write!(
+                f,
+                "no session cookie found, pass it with --cookie, {variable} or {}",
+                file.as_ref()
+                    .map_or(String::from("~/.config/aoc/session"), |path| path
+                        .display()
+                        .to_string())
+            ),
+            Self::InvalidFormat(source) => write!(
+                f,
+                "the session cookie from {source} is not a hexadecimal string of at least 64 characters"
+            ),
+            Self::InsecurePermissions(path) => write!(
+                f,
+                "{} is accessible by other users, restrict it with chmod 600",
+                path.display()
+            ),
+            Self::Io(path, e) => write!(f, "error reading {}: {e}", path.display()),
+            Self::Database(path, e) => write!(f, "error reading {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Checks that the cookie looks like an AoC session cookie, to catch copy/paste errors
+/// such as including the `session=` prefix before making any request.
+pub fn is_valid_cookie(cookie: &str) -> bool {
+    static COOKIE_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^[0-9a-fA-F]{64,}$").expect("cookie regex"));
+    COOKIE_RE.is_match(cookie)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(PathBuf::from)
+}
+
+/// Returns the location of the session cookie file, `$XDG_CONFIG_HOME/aoc/session` or
+/// `~/.config/aoc/session`.
+pub fn config_file() -> Option<PathBuf> {
+    Some(config::dir()?.join("session"))
+}
+
+/// Reads the cookie from a file, which must not be accessible by other users. Returns
+/// None if the file does not exist or is empty.
+pub fn read_cookie_file(path: &Path) -> Result<Option<String>, Error> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(Error::Io(path.to_path_buf(), e)),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o077 != 0 {
+            return Err(Error::InsecurePermissions(path.to_path_buf()));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = metadata;
+    let cookie = fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+    let cookie = cookie.trim();
+    Ok((!cookie.is_empty()).then(|| cookie.to_string()))
+}
+
+/// A copy of a browser cookie database, deleted when dropped.
+struct CookieDatabase {
+    connection: Connection,
+    _dir: TempDir,
+}
+
+/// Returns the path of the file SQLite keeps next to the database, such as its
+/// write-ahead log.
+fn database_file(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Opens a copy of a browser cookie database, as the browser may be running and keep
+/// the database locked. The write-ahead log is copied along, as recent cookies may not
+/// have made it into the database itself yet.
+fn open_cookie_database(path: &Path) -> Result<CookieDatabase, Error> {
+    let dir = tempfile::tempdir().map_err(|e| Error::Io(path.to_path_buf(), e))?;
+    let copy = dir.path().join("cookies.sqlite");
+    fs::copy(path, &copy).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+    let wal = database_file(path, "-wal");
+    match fs::copy(&wal, database_file(&copy, "-wal")) {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(Error::Io(wal, e)),
+    }
+    let connection = Connection::open(&copy).map_err(|e| Error::Database(path.to_path_buf(), e))?;
+    Ok(CookieDatabase {
+        connection,
+        _dir: dir,
+    })
+}
+
+fn firefox_databases(home: &Path) -> Vec<PathBuf> {
+    [
+        home.join(".mozilla/firefox"),
+        home.join("snap/firefox/common/.mozilla/firefox"),
+        home.join("Library/Application Support/Firefox/Profiles"),
+    ]
+    .iter()
+    .filter_map(|dir| fs::read_dir(dir).ok())
+    .flatten()
+    .filter_map(|profile| Some(profile.ok()?.path().join("cookies.sqlite")))
+    .filter(|path| path.exists())
+    .collect()
+}
+
+pub fn read_firefox_cookie(path: &Path) -> Result<Option<String>, Error> {
+    open_cookie_database(path)?
+        .connection
+        .query_row(
+            "SELECT value FROM moz_cookies
+             WHERE host LIKE '%adventofcode.com' AND name = 'session'
+             ORDER BY lastAccessed DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| Error::Database(path.to_path_buf(), e))
+}
+
+fn chromium_databases(home: &Path) -> Vec<PathBuf> {
+    [
+        ".config/chromium",
+        ".config/google-chrome",
+        "Library/Application Support/Chromium",
+        "Library/Application Support/Google/Chrome",
+    ]
+    .iter()
+    .flat_map(|dir| {
+        let profile = home.join(dir).join("Default");
+        [profile.join("Network/Cookies"), profile.join("Cookies")]
+    })
+    .filter(|path| path.exists())
+    .collect()
+}
+
+/// Decrypts a cookie value encrypted by Chromium on Linux without a keyring ("v10").
+/// Values encrypted with a key from the keyring ("v11") are not supported.
+fn decrypt_chromium_value(encrypted: &[u8]) -> Option<String> {
+    let ciphertext = encrypted.strip_prefix(b"v10")?;
+    let mut key = [0u8; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", b"saltysalt", 1, &mut key);
+    let mut buffer = ciphertext.to_vec();
+    let plaintext = cbc::Decryptor::<aes::Aes128>::new(&key.into(), &[b' '; 16].into())
+        .decrypt_padded_mut::<Pkcs7>(&mut buffer)
+        .ok()?;
+    // Recent versions prefix the value with the SHA-256 hash of the cookie's domain.
+    [plaintext, plaintext.get(32..).unwrap_or_default()]
+        .into_iter()
+        .filter_map(|value| String::from_utf8(value.to_vec()).ok())
+        .find(|value| is_valid_cookie(value))
+}
+
+/// Explains why a cookie value encrypted by Chromium could not be decrypted.
+fn undecryptable_reason(encrypted: &[u8]) -> &'static str {
+    if encrypted.starts_with(b"v11") {
+        "it is encrypted with a key from the desktop keyring, which is not supported"
+    } else if encrypted.starts_with(b"v10") && cfg!(target_os = "macos") {
+        "it is encrypted with a key from the macOS Keychain, which is not supported"
+    } else if encrypted.starts_with(b"v10") {
+        "it does not decrypt with the fixed key Chromium uses on Linux without a keyring"
+    } else {
+        "its encryption scheme is not known"
+    }
+}
+
+pub fn read_chromium_cookie(path: &Path) -> Result<Option<String>, Error> {
+    let row: Option<(String, Vec<u8>)> = open_cookie_database(path)?
+        .connection
+        .query_row(
+            "SELECT value, encrypted_value FROM cookies
+             WHERE host_key LIKE '%adventofcode.com' AND name = 'session'
+             ORDER BY last_access_utc DESC LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| Error::Database(path.to_path_buf(), e))?;
+    Ok(row.and_then(|(value, encrypted)| {
+        if !value.is_empty() {
+            Some(value)
+        } else {
+            let decrypted = decrypt_chromium_value(&encrypted);
+            if decrypted.is_none() {
+                log::warn!(
+                    "cannot decrypt the session cookie in {}: {}",
+                    path.display(),
+                    undecryptable_reason(&encrypted)
+                );
+            }
+            decrypted
+        }
+    }))
+}
+
+fn find_in_browsers(home: &Path) -> Option<(String, CookieSource)> {
+    for path in firefox_databases(home) {
+        match read_firefox_cookie(&path) {
+            Ok(Some(cookie)) => return Some((cookie, CookieSource::Firefox(path))),
+            Ok(None) => log::debug!("no session cookie in {}", path.display()),
+            Err(e) => log::warn!("{e}"),
+        }
+    }
+    for path in chromium_databases(home) {
+        match read_chromium_cookie(&path) {
+            Ok(Some(cookie)) => return Some((cookie, CookieSource::Chromium(path))),
+            Ok(None) => log::debug!("no session cookie in {}", path.display()),
+            Err(e) => log::warn!("{e}"),
+        }
+    }
+    None
+}
+
+/// Looks for the session cookie of the profile in the command line argument, the
+/// profile's environment variable and cookie file and, for the default profile, the
+/// cookie databases of Firefox and Chromium, in this order, and validates the first one
+/// found. An empty environment variable counts as not set.
+pub fn find_cookie(
+    command_line: Option<&str>,
+    profile: &Profile,
+) -> Result<(String, CookieSource), Error> {
+    let variable = profile.cookie_variable();
+    let file = profile.cookie_file();
+    let found = if let Some(cookie) = command_line {
+        Some((cookie.to_string(), CookieSource::CommandLine))
+    } else if let Ok(cookie) = env::var(&variable)
+        && !cookie.trim().is_empty()
+    {
+        Some((cookie, CookieSource::Environment(variable.clone())))
+    } else if let Some(path) = &file
+        && let Some(cookie) = read_cookie_file(path)?
+    {
+        Some((cookie, CookieSource::File(path.clone())))
+    } else if profile.uses_browser_cookies() {
+        home_dir().and_then(|home| find_in_browsers(&home))
+    } else {
+        None
+    };
+    match found {
+        Some((cookie, source)) if is_valid_cookie(&cookie) => Ok((cookie, source)),
+        Some((_, source)) => Err(Error::InvalidFormat(source)),
+        None => Err(Error::NotFound(variable, file)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aes::cipher::BlockEncryptMut;
+
+    use super::*;
+
+    const COOKIE: &str = "53616c7465645f5f0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn validates_cookie_format() {
+        assert!(is_valid_cookie(COOKIE));
+        assert!(!is_valid_cookie(&format!("session={COOKIE}")));
+        assert!(!is_valid_cookie("deadbeef"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cookie_file_must_be_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("creating config directory");
+        let path = dir.path().join("session");
+        assert!(matches!(read_cookie_file(&path), Ok(None)));
+        fs::write(&path, format!("{COOKIE}\n")).expect("writing cookie file");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).expect("chmod");
+        assert!(matches!(
+            read_cookie_file(&path),
+            Err(Error::InsecurePermissions(_))
+        ));
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).expect("chmod");
+        assert_eq!(
+            read_cookie_file(&path).expect("reading cookie file"),
+            Some(String::from(COOKIE))
+        );
+        fs::write(&path, " \n").expect("writing cookie file");
+        assert!(matches!(read_cookie_file(&path), Ok(None)));
+    }
+
+    #[test]
+    fn reads_firefox_database() {
+        let dir = tempfile::tempdir().expect("creating profile directory");
+        let path = dir.path().join("cookies.sqlite");
+        let db = Connection::open(&path).expect("creating database");
+        db.execute_batch(&format!(
+            "CREATE TABLE moz_cookies (name TEXT, value TEXT, host TEXT, lastAccessed INTEGER);
+             INSERT INTO moz_cookies VALUES ('session', 'other', '.example.com', 3);
+             INSERT INTO moz_cookies VALUES ('session', 'stale', '.adventofcode.com', 1);
+             INSERT INTO moz_cookies VALUES ('session', '{COOKIE}', '.adventofcode.com', 2);"
+        ))
+        .expect("populating database");
+        drop(db);
+        assert_eq!(
+            read_firefox_cookie(&path).expect("reading database"),
+            Some(String::from(COOKIE))
+        );
+    }
+
+    #[test]
+    fn reads_write_ahead_log_of_open_database() {
+        let dir = tempfile::tempdir().expect("creating profile directory");
+        let path = dir.path().join("cookies.sqlite");
+        let db = Connection::open(&path).expect("creating database");
+        db.execute_batch(&format!(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA wal_autocheckpoint = 0;
+             CREATE TABLE moz_cookies (name TEXT, value TEXT, host TEXT, lastAccessed INTEGER);
+             INSERT INTO moz_cookies VALUES ('session', '{COOKIE}', '.adventofcode.com', 1);"
+        ))
+        .expect("populating database");
+        assert!(database_file(&path, "-wal").exists());
+        assert_eq!(
+            read_firefox_cookie(&path).expect("reading database"),
+            Some(String::from(COOKIE))
+        );
+        drop(db);
+    }
+
+    #[test]
+    fn reads_encrypted_chromium_database() {
+        let mut key = [0u8; 16];
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", b"saltysalt", 1, &mut key);
+        let plaintext = [[0xaa; 32].as_slice(), COOKIE.as_bytes()].concat();
+        let mut buffer = vec![0u8; plaintext.len() + 16];
+        buffer[..plaintext.len()].copy_from_slice(&plaintext);
+        let ciphertext = cbc::Encryptor::<aes::Aes128>::new(&key.into(), &[b' '; 16].into())
+            .encrypt_padded_mut::<Pkcs7>(&mut buffer, plaintext.len())
+            .expect("encrypting cookie");
+        let encrypted = [b"v10".as_slice(), ciphertext].concat();
+
+        let dir = tempfile::tempdir().expect("creating profile directory");
+        let path = dir.path().join("Cookies");
+        let db = Connection::open(&path).expect("creating database");
+        db.execute_batch(
+            "CREATE TABLE cookies (host_key TEXT, name TEXT, value TEXT,
+                                   encrypted_value BLOB, last_access_utc INTEGER);",
+        )
+        .expect("creating table");
+        db.execute(
+            "INSERT INTO cookies VALUES ('.adventofcode.com', 'session', '', ?1, 1)",
+            [&encrypted],
+        )
+        .expect("populating database");
+        drop(db);
+        assert_eq!(
+            read_chromium_cookie(&path).expect("reading database"),
+            Some(String::from(COOKIE))
+        );
+    }
+
+    #[test]
+    fn explains_unsupported_encryption() {
+        assert!(undecryptable_reason(b"v11abc").contains("keyring"));
+        assert!(undecryptable_reason(b"xyz").contains("not known"));
+        let v10 = undecryptable_reason(b"v10abc");
+        assert_eq!(v10.contains("Keychain"), cfg!(target_os = "macos"));
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::aocclient::AocClient;
+use crate::aocclient::Error;
+use crate::aocclient::ValidationResult;
+
+/// Where puzzle inputs come from and where answers are checked.
+pub trait PuzzleSource {
+    /// Returns the puzzle input for the day, from a cache if the source has one.
+    fn get_input(&self, year: i16, day: i8) -> Result<String, Error>;
+
+    /// Returns the puzzle input for the day, bypassing any cache.
+    fn fetch_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        self.get_input(year, day)
+    }
+
+    fn submit_answer(
+        &self,
+        year: i16,
+        day: i8,
+        level: i8,
+        answer: &str,
+    ) -> Result<ValidationResult, Error>;
+}
+
+impl PuzzleSource for AocClient {
+    fn get_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        self.get_puzzle_input(year, day)
+    }
+
+    fn fetch_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        self.fetch_puzzle_input(year, day)
+    }
+
+    fn submit_answer(
+        &self,
+        year: i16,
+        day: i8,
+        level: i8,
+        answer: &str,
+    ) -> Result<ValidationResult, Error> {
+        AocClient::submit_answer(self, year, day, level, answer)
+    }
+}
+
+/// Inputs laid out like the input cache (`<root>/<year>/dayNN.txt`), with answers
+/// checked against the key in `<root>/answers.toml`, which has entries like
+/// `"2025/1/1" = "1078"`.
+pub struct DirectorySource {
+    root: PathBuf,
+    answers: HashMap<String, String>,
+}
+
+impl DirectorySource {
+    /// Opens the directory. A missing answer key is treated as empty, so that the
+    /// directory can be used for inputs alone.
+    pub fn new(root: impl Into<PathBuf>) -> Result<DirectorySource, Error> {
+        let root = root.into();
+        let answers = match fs::read_to_string(root.join("answers.toml")) {
+            Ok(key) => toml::from_str(&key).map_err(|e| Error::AnswerKey(e.to_string()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(DirectorySource { root, answers })
+    }
+}
+
+impl PuzzleSource for DirectorySource {
+    fn get_input(&self, year: i16, day: i8) -> Result<String, Error> {
+        let path = self
+            .root
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"));
+        fs::read_to_string(&path)
+            .map_err(|e| Error::Io(io::Error::new(e.kind(), format!("{}: {e}", path.display()))))
+    }
+
+    fn submit_answer(
+        &self,
+        year: i16,
+        day: i8,
+        level: i8,
+        answer: &str,
+    ) -> Result<ValidationResult, Error> {
+        let key = format!("{year}/{day}/{level}");
+        let expected = self
+            .answers
+            .get(&key)
+            .ok_or_else(|| Error::AnswerKey(format!("no answer for {key}")))?;
+        let answer = answer.trim();
+        if answer == expected {
+            return Ok(ValidationResult::Accepted);
+        }
+        Ok(match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer < expected => ValidationResult::RejectedTooLow,
+            (Ok(answer), Ok(expected)) if answer > expected => ValidationResult::RejectedTooHigh,
+            _ => ValidationResult::Rejected,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_directory() {
+        let dir = tempfile::tempdir().expect("creating source directory");
+        fs::create_dir(dir.path().join("2025")).expect("creating year directory");
+        fs::write(dir.path().join("2025/day03.txt"), "987654321111111\n").expect("writing input");
+        fs::write(
+            dir.path().join("answers.toml"),
+            "\"2025/3/1\" = \"98\"\n\"2025/3/2\" = \"abc\"\n",
+        )
+        .expect("writing answer key");
+        let source = DirectorySource::new(dir.path()).expect("opening source");
+        assert_eq!(
+            source.get_input(2025, 3).expect("reading input"),
+            "987654321111111\n"
+        );
+        let missing = source
+            .get_input(2025, 4)
+            .expect_err("reading missing input");
+        assert!(matches!(missing, Error::Io(_)));
+        assert!(missing.to_string().contains("day04.txt"));
+        let submit = |level, answer| {
+            source
+                .submit_answer(2025, 3, level, answer)
+                .expect("checking answer")
+        };
+        assert_eq!(submit(1, "98"), ValidationResult::Accepted);
+        assert_eq!(submit(1, "97"), ValidationResult::RejectedTooLow);
+        assert_eq!(submit(1, "99"), ValidationResult::RejectedTooHigh);
+        assert_eq!(submit(2, "abd"), ValidationResult::Rejected);
+        assert!(matches!(
+            source.submit_answer(2025, 4, 1, "1"),
+            Err(Error::AnswerKey(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_answer_key() {
+        let dir = tempfile::tempdir().expect("creating source directory");
+        fs::write(dir.path().join("answers.toml"), "2025/3/1 = 98\n").expect("writing answer key");
+        assert!(matches!(
+            DirectorySource::new(dir.path()),
+            Err(Error::AnswerKey(_))
+        ));
+    }
+}
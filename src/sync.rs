@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::aocclient::AocClient;
+use crate::aocclient::Error;
+
+/// Counts of what the sync command did.
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncSummary {
+    pub inputs: usize,
+    pub descriptions: usize,
+    pub cached: usize,
+    pub failed: usize,
+}
+
+impl fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "downloaded {} inputs and {} descriptions, {} files already cached, {} failed",
+            self.inputs, self.descriptions, self.cached, self.failed
+        )
+    }
+}
+
+impl SyncSummary {
+    /// Reports the error and tells whether syncing should stop, because every further
+    /// request would fail the same way.
+    fn fail(&mut self, what: &str, year: i16, day: i8, e: &Error) -> bool {
+        crate::report_error(
+            &format!("error downloading the {what} of {year} day {day}"),
+            e,
+        );
+        self.failed += 1;
+        matches!(e, Error::SessionExpired | Error::Offline(_))
+    }
+}
+
+/// Downloads the inputs and descriptions of the days that are not cached, including
+/// descriptions cached without part two. Requests are spaced out by the throttle.
+pub fn sync_cache(client: &AocClient, year: i16, days: &[i8], refresh: bool) -> SyncSummary {
+    let mut summary = SyncSummary::default();
+    for &day in days {
+        if !refresh && client.is_input_cached(year, day) {
+            summary.cached += 1;
+        } else {
+            log::info!("downloading the input of {year} day {day}...");
+            match client.fetch_puzzle_input(year, day) {
+                Ok(_) => summary.inputs += 1,
+                Err(e) if summary.fail("input", year, day, &e) => break,
+                Err(_) => {}
+            }
+        }
+        if !refresh && client.is_description_cached(year, day) {
+            summary.cached += 1;
+        } else {
+            log::info!("downloading the description of {year} day {day}...");
+            match client.fetch_puzzle_description(year, day) {
+                Ok(_) => summary.descriptions += 1,
+                Err(e) if summary.fail("description", year, day, &e) => break,
+                Err(_) => {}
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use httptest::Expectation;
+    use httptest::Server;
+    use httptest::matchers::request;
+    use httptest::responders::status_code;
+
+    use super::*;
+    use crate::aocclient::ClientOptions;
+
+    const PAGE: &str = r#"<main><article class="day-desc"><h2>--- Day 2: Test ---</h2><p>One.</p></article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Two.</p></article></main>"#;
+
+    fn client(server: &Server, cache: &tempfile::TempDir, offline: bool) -> AocClient {
+        let url = server.url("/");
+        let base_url = format!("{}://{}/", url.scheme().unwrap(), url.authority().unwrap());
+        let mut client = AocClient::new_with_base(
+            &base_url,
+            ClientOptions {
+                offline,
+                ..Default::default()
+            },
+        )
+        .expect("creating AoC client");
+        client.set_cookie("deadbeef");
+        client.set_input_cache(cache.path());
+        client
+    }
+
+    #[test]
+    fn downloads_only_what_can_change() {
+        let server = Server::run();
+        for (path, body) in [("/2024/day/2/input", "2\n"), ("/2024/day/2", PAGE)] {
+            server.expect(
+                Expectation::matching(request::path(path))
+                    .times(1)
+                    .respond_with(status_code(200).body(body)),
+            );
+        }
+        let cache = tempfile::tempdir().expect("creating cache directory");
+        let year_dir = cache.path().join("2024");
+        fs::create_dir_all(&year_dir).expect("creating cache");
+        fs::write(year_dir.join("day01.txt"), "1\n").expect("writing input");
+        fs::write(year_dir.join("day01.md"), "--- Part Two ---\n").expect("writing description");
+        // Cached before part one was solved, so part two is still missing.
+        fs::write(year_dir.join("day02.md"), "--- Day 2 ---\n").expect("writing description");
+
+        let client = client(&server, &cache, false);
+        assert_eq!(
+            sync_cache(&client, 2024, &[1, 2], false),
+            SyncSummary {
+                inputs: 1,
+                descriptions: 1,
+                cached: 2,
+                failed: 0
+            }
+        );
+        assert!(client.is_description_cached(2024, 2));
+    }
+
+    #[test]
+    fn stops_when_session_expired() {
+        let server = Server::run();
+        server.expect(
+            Expectation::matching(request::path("/2024/day/1/input"))
+                .times(1)
+                .respond_with(status_code(400)),
+        );
+        let cache = tempfile::tempdir().expect("creating cache directory");
+        let client = client(&server, &cache, false);
+        assert_eq!(
+            sync_cache(&client, 2024, &[1, 2, 3], false),
+            SyncSummary {
+                failed: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn stops_when_offline() {
+        let server = Server::run();
+        let cache = tempfile::tempdir().expect("creating cache directory");
+        let client = client(&server, &cache, true);
+        let summary = sync_cache(&client, 2024, &[1, 2, 3], false);
+        assert_eq!(
+            summary,
+            SyncSummary {
+                failed: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "downloaded 0 inputs and 0 descriptions, 0 files already cached, 1 failed"
+        );
+    }
+}